@@ -1,10 +1,16 @@
 //! Module containing the [`LayerExt`] utility trait.
 
-use std::{error::Error, str::FromStr};
+use std::{error::Error, marker::PhantomData, ops::RangeBounds, str::FromStr};
 
 use crate::{
+    descriptor::{ConfigValueDescriptor, DescribeLayer},
+    error::LayerError,
     layer::Layer,
-    layers::{Cached, FileRead, OrDefault, Parsed, parsed::ParseFn},
+    layers::{
+        ByteSize, Cached, Choice, FileRead, FlagBool, InRange, Optional, OrDefault,
+        OrDefaultStrict, Parsed, TypedParsed, parsed::ParseFn,
+    },
+    report::Explanation,
 };
 
 /// Utility trait for building configuration value types.
@@ -48,10 +54,25 @@ pub trait LayerExt: Sized {
     fn parsed<T>(self, parse_fn: ParseFn<T>) -> Parsed<T, Self>
     where
         Self: Layer,
+    {
+        self.parsed_with(parse_fn)
+    }
+
+    /// Marks the configuration value to be parsed, using the provided closure.
+    ///
+    /// Unlike [`parsed`][1], the closure may capture its environment, e.g. a base directory
+    /// computed at runtime.
+    ///
+    /// [1]: LayerExt::parsed
+    fn parsed_with<T, F>(self, parse_fn: F) -> Parsed<T, Self, F>
+    where
+        Self: Layer,
+        F: Fn(&str) -> Result<T, Box<dyn Error + Send + Sync>>,
     {
         Parsed {
             parse_fn,
             var: self,
+            _marker: PhantomData,
         }
     }
 
@@ -64,34 +85,216 @@ pub trait LayerExt: Sized {
     fn parsed_from_str<T>(self) -> Parsed<T, Self>
     where
         Self: Layer,
-        T: FromStr<Err: Error + 'static>,
+        T: FromStr<Err: Error + Send + Sync + 'static>,
     {
         self.parsed(|input| {
             input
                 .parse::<T>()
-                .map_err(|e| Box::new(e) as Box<dyn Error>)
+                .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)
         })
     }
 
+    /// Marks the configuration value to be parsed, using the provided closure, and keeping the
+    /// concrete type of the parse error.
+    ///
+    /// See the [`TypedParsed`] documentation for more information.
+    fn parsed_typed<T, E, F>(self, parse_fn: F) -> TypedParsed<T, E, Self, F>
+    where
+        Self: Layer,
+        F: Fn(&str) -> Result<T, E>,
+    {
+        TypedParsed {
+            parse_fn,
+            var: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Marks the configuration value to be parsed, using the [`FromStr`] trait implementation,
+    /// and keeping the concrete type of the parse error.
+    ///
+    /// See the [`TypedParsed`] documentation for more information.
+    fn parsed_typed_from_str<T>(self) -> TypedParsed<T, <T as FromStr>::Err, Self>
+    where
+        Self: Layer,
+        T: FromStr,
+    {
+        self.parsed_typed(<T as FromStr>::from_str)
+    }
+
+    /// Marks the configuration value to be parsed as a size in bytes, e.g. `512MiB` or `10MB`.
+    ///
+    /// See the [`ByteSize`] documentation for the accepted format.
+    fn byte_size(self) -> ByteSize<Self>
+    where
+        Self: Layer,
+    {
+        ByteSize { var: self }
+    }
+
+    /// Marks the configuration value to only accept the provided values.
+    ///
+    /// See the [`Choice`] documentation for more information.
+    fn one_of(self, choices: &'static [&'static str]) -> Choice<Self>
+    where
+        Self: Layer,
+    {
+        Choice { var: self, choices }
+    }
+
+    /// Marks the configuration value to only accept values in the provided range, e.g. `1..=16`.
+    ///
+    /// See the [`InRange`] documentation for more information.
+    fn in_range<R>(self, range: R) -> InRange<Self>
+    where
+        Self: Layer<Output: Clone>,
+        R: RangeBounds<<Self as Layer>::Output>,
+    {
+        InRange {
+            start: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+            var: self,
+        }
+    }
+
+    /// Marks the configuration value to be parsed as a boolean flag, accepting the usual truthy
+    /// and falsy spellings (`1`, `yes`, `on`, `0`, `no`, `off`, ...), case-insensitively.
+    ///
+    /// Note: if you wish the flag to be `true` whenever the environment variable is set, you may
+    /// use the [`flag_present`][1] method instead.
+    ///
+    /// [1]: LayerExt::flag_present
+    fn flag_bool(self) -> FlagBool<Self>
+    where
+        Self: Layer,
+    {
+        FlagBool {
+            var: self,
+            presence_only: false,
+        }
+    }
+
+    /// Marks the configuration value to be a boolean flag that is `true` as soon as the
+    /// environment variable is set, even to an empty value, and `false` otherwise.
+    fn flag_present(self) -> FlagBool<Self>
+    where
+        Self: Layer,
+    {
+        FlagBool {
+            var: self,
+            presence_only: true,
+        }
+    }
+
+    /// Marks the configuration value to be optional, returning `None` if the environment variable
+    /// isn't set.
+    ///
+    /// Other errors, such as an invalid value, are still returned.
+    fn optional(self) -> Optional<Self>
+    where
+        Self: Layer,
+    {
+        Optional { var: self }
+    }
+
     /// Marks the configuration value to fallback to a default value on read, using the provided
     /// function.
+    ///
+    /// Note: the default value is used on any read error, including an invalid value. If you wish
+    /// to only fallback when the environment variable isn't set, you may use the
+    /// [`or_default_val_strict`][1] method instead.
+    ///
+    /// [1]: LayerExt::or_default_val_strict
     fn or_default_val(self, default_fn: fn() -> <Self as Layer>::Output) -> OrDefault<Self>
     where
         Self: Layer,
+    {
+        self.or_default_with(default_fn)
+    }
+
+    /// Marks the configuration value to fallback to the default value of the output on read.
+    #[inline]
+    fn or_default(self) -> OrDefault<Self>
+    where
+        Self: Layer<Output: Default>,
+    {
+        self.or_default_val(Default::default)
+    }
+
+    /// Marks the configuration value to fallback to a default value on read, using the provided
+    /// closure.
+    ///
+    /// Unlike [`or_default_val`][1], the closure may capture its environment, e.g. a default
+    /// value computed at startup.
+    ///
+    /// [1]: LayerExt::or_default_val
+    fn or_default_with<F>(self, default_fn: F) -> OrDefault<Self, F>
+    where
+        Self: Layer,
+        F: Fn() -> <Self as Layer>::Output,
     {
         OrDefault {
             var: self,
             default_fn,
+            eq_fn: None,
+            descriptor: None,
         }
     }
 
-    /// Marks the configuration value to fallback to the default value of the output on read.
+    /// Marks the configuration value to fallback to a default value on read, using the provided
+    /// function, only if the environment variable isn't set.
+    ///
+    /// Other errors, such as an invalid value, are still returned.
+    fn or_default_val_strict(
+        self,
+        default_fn: fn() -> <Self as Layer>::Output,
+    ) -> OrDefaultStrict<Self>
+    where
+        Self: Layer,
+    {
+        self.or_default_with_strict(default_fn)
+    }
+
+    /// Marks the configuration value to fallback to the default value of the output on read, only
+    /// if the environment variable isn't set.
     #[inline]
-    fn or_default(self) -> OrDefault<Self>
+    fn or_default_strict(self) -> OrDefaultStrict<Self>
     where
         Self: Layer<Output: Default>,
     {
-        self.or_default_val(Default::default)
+        self.or_default_val_strict(Default::default)
+    }
+
+    /// Marks the configuration value to fallback to a default value on read, using the provided
+    /// closure, only if the environment variable isn't set.
+    ///
+    /// Unlike [`or_default_val_strict`][1], the closure may capture its environment, e.g. a
+    /// default value computed at startup.
+    ///
+    /// [1]: LayerExt::or_default_val_strict
+    fn or_default_with_strict<F>(self, default_fn: F) -> OrDefaultStrict<Self, F>
+    where
+        Self: Layer,
+        F: Fn() -> <Self as Layer>::Output,
+    {
+        OrDefaultStrict {
+            var: self,
+            default_fn,
+            eq_fn: None,
+            descriptor: None,
+        }
+    }
+
+    /// Reads the configuration value, and returns the explanation of how it was resolved.
+    ///
+    /// See [`Explanation`] for more information.
+    fn explain<'a>(&'a self) -> Explanation<'a>
+    where
+        &'a Self: Layer<Error: LayerError>,
+        Box<dyn Error + Send + Sync + 'a>: From<<&'a Self as Layer>::Error>,
+        Self: ConfigValueDescriptor + DescribeLayer,
+    {
+        Explanation::from_config(self)
     }
 }
 
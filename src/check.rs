@@ -0,0 +1,103 @@
+//! Compile-time checks of the configurations generated by [`make_config!`](crate::make_config).
+
+/// The names of the environment variables of a configuration, with its nested configurations.
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum VarNames {
+    Var {
+        name: &'static str,
+        alias: Option<&'static str>,
+    },
+    Nested(&'static [VarNames]),
+}
+
+/// Panics if an environment variable name is declared several times in the provided tree,
+/// either as the name or as the deprecated alias of a field.
+///
+/// It is evaluated in a constant context, so the panic becomes a compilation error.
+pub const fn check_var_names(names: &[VarNames]) {
+    check_tree(names, names);
+}
+
+const fn check_tree(names: &[VarNames], root: &[VarNames]) {
+    let mut i = 0;
+    while i < names.len() {
+        match &names[i] {
+            VarNames::Var { name, alias } => {
+                if occurrences(root, name) > 1 {
+                    duplicate(name);
+                }
+                if let Some(alias) = alias
+                    && occurrences(root, alias) > 1
+                {
+                    duplicate(alias);
+                }
+            }
+            VarNames::Nested(nested) => check_tree(nested, root),
+        }
+        i += 1;
+    }
+}
+
+const fn occurrences(names: &[VarNames], name: &str) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < names.len() {
+        count += match &names[i] {
+            VarNames::Var { name: other, alias } => {
+                let alias_count = match alias {
+                    Some(alias) => str_eq(alias, name) as usize,
+                    None => 0,
+                };
+                str_eq(other, name) as usize + alias_count
+            }
+            VarNames::Nested(nested) => occurrences(nested, name),
+        };
+        i += 1;
+    }
+    count
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn duplicate(name: &str) -> ! {
+    // Constant panics can only format a single string, so the message is written in a buffer
+    const PREFIX: &[u8] = b"the environment variable `";
+    const SUFFIX: &[u8] = b"` is declared by several fields of the configuration";
+
+    let mut buf = [0; 256];
+    let mut len = 0;
+    let parts = [PREFIX, name.as_bytes(), SUFFIX];
+    let mut i = 0;
+    while i < parts.len() {
+        let mut j = 0;
+        while j < parts[i].len() && len < buf.len() {
+            buf[len] = parts[i][j];
+            len += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+
+    let (msg, _) = buf.split_at(len);
+    match str::from_utf8(msg) {
+        Ok(msg) => panic!("{}", msg),
+        // The name was truncated in the middle of a character
+        Err(_) => {
+            panic!("an environment variable is declared by several fields of the configuration")
+        }
+    }
+}
@@ -1,15 +1,119 @@
 //! Module containing everything related to environment value descriptors.
 
-use std::fmt;
+use std::{any, borrow::Cow, fmt, ops::Bound, vec};
+
+use crate::{
+    check::VarNames,
+    layers::{
+        flag_bool::{FALSY_VALUES, TRUTHY_VALUES},
+        in_range::fmt_bounds,
+    },
+};
+
+/// The placeholder of the value of the secret variables.
+pub(crate) const SECRET_PLACEHOLDER: &str = "<secret>";
 
 /// Describes a configuration value.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VarDescriptor {
     /// The name of the environment variable this configuration value is read from.
     pub var_name: &'static str,
 
     pub(crate) description: Option<&'static str>,
-    pub(crate) default_val_fmt: Option<&'static str>,
+    pub(crate) default_val_fmt: Option<Cow<'static, str>>,
+    pub(crate) example: Option<&'static str>,
+    pub(crate) required: bool,
+    pub(crate) secret: bool,
+    pub(crate) type_name: Option<&'static str>,
+    pub(crate) tags: &'static [&'static str],
+    pub(crate) deprecated: Option<&'static str>,
+    pub(crate) deprecated_alias: Option<&'static str>,
+}
+
+impl VarDescriptor {
+    pub(crate) const fn new(var_name: &'static str) -> Self {
+        Self {
+            var_name,
+            description: None,
+            default_val_fmt: None,
+            example: None,
+            required: true,
+            secret: false,
+            type_name: None,
+            tags: &[],
+            deprecated: None,
+            deprecated_alias: None,
+        }
+    }
+
+    /// Returns the description of the configuration value.
+    #[inline]
+    pub fn description(&self) -> Option<&'static str> {
+        self.description
+    }
+
+    /// Returns the text of the default value of the configuration value.
+    ///
+    /// With the [`make_config!`](crate::make_config) macro, it is taken from the default value
+    /// itself if its type implements [`Display`](fmt::Display) or [`Debug`](fmt::Debug), unless
+    /// the `default_val_fmt` key is provided. It is never taken from the default value of a
+    /// secret variable.
+    #[inline]
+    pub fn default_val_fmt(&self) -> Option<&str> {
+        self.default_val_fmt.as_deref()
+    }
+
+    /// Returns an example value of the environment variable.
+    #[inline]
+    pub fn example(&self) -> Option<&'static str> {
+        self.example
+    }
+
+    /// Returns whether the environment variable is required to be set.
+    ///
+    /// It is `true` unless specified otherwise.
+    #[inline]
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    /// Returns whether the value of the environment variable is a secret, e.g. a password.
+    #[inline]
+    pub fn is_secret(&self) -> bool {
+        self.secret
+    }
+
+    /// Returns the name of the output type of the configuration value, if specified.
+    ///
+    /// See [`LayerDescription::output_type`] for the name of the type taken from the layers.
+    #[inline]
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// Returns the tags of the configuration value.
+    #[inline]
+    pub fn tags(&self) -> &'static [&'static str] {
+        self.tags
+    }
+
+    /// Returns the deprecation note of the configuration value, if it is deprecated.
+    #[inline]
+    pub fn deprecated(&self) -> Option<&'static str> {
+        self.deprecated
+    }
+
+    /// Returns the deprecated name of the environment variable, read if it isn't set.
+    #[inline]
+    pub fn deprecated_alias(&self) -> Option<&'static str> {
+        self.deprecated_alias
+    }
+
+    /// Returns the names of the environment variables read for the configuration value, i.e. its
+    /// name and its deprecated alias.
+    pub(crate) fn var_names(&self) -> impl Iterator<Item = &'static str> {
+        std::iter::once(self.var_name).chain(self.deprecated_alias)
+    }
 }
 
 impl fmt::Display for VarDescriptor {
@@ -18,8 +122,13 @@ impl fmt::Display for VarDescriptor {
         if let Some(desc) = self.description {
             write!(f, ": {desc}")?;
         }
-        if let Some(default_val) = self.default_val_fmt {
-            write!(f, " (default: {default_val})")?;
+        match &self.default_val_fmt {
+            Some(_) if self.secret => write!(f, " (default: {SECRET_PLACEHOLDER})")?,
+            Some(default_val) => write!(f, " (default: {default_val})")?,
+            None => {}
+        }
+        if let Some(note) = self.deprecated {
+            write!(f, " (deprecated: {note})")?;
         }
         Ok(())
     }
@@ -38,10 +147,528 @@ impl<T: ConfigValueDescriptor> ConfigValueDescriptor for &T {
     }
 }
 
+/// Describes the chain of layers of a configuration value.
+///
+/// It contains a step for each layer, from the innermost one to the outermost one, and the name
+/// of the output type of the chain. It is displayed with the steps separated by arrows, e.g.
+/// `text → file content → parse Duration → default 5s → cached`.
+///
+/// To get it, see [`DescribeLayer::describe`].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::prelude::*;
+/// let timeout = TextVar::from_var_name("TIMEOUT_SECS")
+///   .default_fmt_val("5")
+///   .parsed_from_str::<u64>()
+///   .or_default_val(|| 5);
+///
+/// let desc = timeout.describe();
+/// assert_eq!(desc.to_string(), "text → parse u64 → default 5");
+/// assert_eq!(desc.output_type(), "u64");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LayerDescription {
+    steps: Vec<Cow<'static, str>>,
+    output_type: String,
+    constraints: Vec<Constraint>,
+    optional: bool,
+}
+
+impl LayerDescription {
+    /// Returns an empty description.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the step of a layer with the output type `O`.
+    ///
+    /// This is meant to be called by the [`DescribeLayer`] implementations, after describing the
+    /// inner layer.
+    pub fn push<O: ?Sized>(&mut self, step: impl Into<Cow<'static, str>>) {
+        self.steps.push(step.into());
+        self.output_type = short_type_name(any::type_name::<O>());
+    }
+
+    /// Returns the steps of the chain, from the innermost layer to the outermost one.
+    #[inline]
+    pub fn steps(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.steps.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns the name of the output type of the chain, without its module path.
+    #[inline]
+    pub fn output_type(&self) -> &str {
+        &self.output_type
+    }
+
+    /// Adds a constraint that the value of the environment variable must satisfy.
+    #[inline]
+    pub fn push_constraint(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+    }
+
+    /// Returns the constraints that the value of the environment variable must satisfy.
+    #[inline]
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Marks the environment variable as optional, i.e. the read doesn't fail if it isn't set.
+    #[inline]
+    pub fn set_optional(&mut self) {
+        self.optional = true;
+    }
+
+    /// Returns whether the read doesn't fail if the environment variable isn't set, e.g. because
+    /// of a default value.
+    #[inline]
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+}
+
+/// A constraint on the value of an environment variable, added by a layer to its
+/// [description](LayerDescription).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Constraint {
+    /// The value must be the path to a readable file.
+    FilePath,
+    /// The value must be parsable to the type with the provided name.
+    Parsable {
+        /// The name of the type, without its module path.
+        type_name: String,
+    },
+    /// The value must be a size in bytes, e.g. `512MiB`.
+    ByteSize,
+    /// The value must be one of the [truthy][1] or [falsy][2] spellings of a boolean flag.
+    ///
+    /// [1]: crate::layers::flag_bool::TRUTHY_VALUES
+    /// [2]: crate::layers::flag_bool::FALSY_VALUES
+    Flag,
+    /// The value must be one of the provided values.
+    OneOf {
+        /// The allowed values.
+        values: &'static [&'static str],
+    },
+    /// The value must be in the provided range.
+    Range {
+        /// The lower bound of the range, formatted.
+        start: Bound<String>,
+        /// The upper bound of the range, formatted.
+        end: Bound<String>,
+    },
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FilePath => f.write_str("path to a readable file"),
+            Self::Parsable { type_name } => write!(f, "valid {type_name}"),
+            Self::ByteSize => f.write_str("size in bytes, e.g. 512MiB"),
+            Self::Flag => {
+                f.write_str("one of ")?;
+                let values = TRUTHY_VALUES.iter().chain(FALSY_VALUES);
+                for (i, value) in values.enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(value)?;
+                }
+                Ok(())
+            }
+            Self::OneOf { values } => write!(f, "one of {}", values.join(", ")),
+            Self::Range { start, end } => fmt_bounds(f, start, end),
+        }
+    }
+}
+
+impl fmt::Display for LayerDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" → ")?;
+            }
+            f.write_str(step)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents layers able to describe themselves.
+///
+/// It is implemented by all the [layers](crate::layers) of the crate. A custom layer may rely on
+/// the default implementation with an empty `impl DescribeLayer for MyLayer {}`.
+pub trait DescribeLayer {
+    /// Adds the description of this layer to the provided one.
+    ///
+    /// Layers wrapping another one should describe it first.
+    ///
+    /// The default implementation adds a step named after the type of the layer, without its
+    /// module path, and leaves the output type unchanged.
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        desc.steps
+            .push(short_type_name(any::type_name::<Self>()).into());
+    }
+
+    /// Returns the description of the chain of layers.
+    fn describe(&self) -> LayerDescription {
+        let mut desc = LayerDescription::new();
+        self.describe_layer(&mut desc);
+        desc
+    }
+}
+
+impl<T: DescribeLayer> DescribeLayer for &T {
+    #[inline(always)]
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        <T as DescribeLayer>::describe_layer(self, desc)
+    }
+}
+
+/// Removes the module paths from a type name, e.g. `core::option::Option<alloc::string::String>`
+/// becomes `Option<String>`.
+pub(crate) fn short_type_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut segment_start = 0;
+    for (i, c) in name.char_indices() {
+        if c.is_alphanumeric() || c == '_' {
+            continue;
+        }
+        if c == ':' {
+            // Drops the segment, which is followed by `::`
+            segment_start = i + 1;
+            continue;
+        }
+        out.push_str(&name[segment_start..i]);
+        out.push(c);
+        segment_start = i + c.len_utf8();
+    }
+    out.push_str(&name[segment_start..]);
+    out
+}
+
 /// Represents types able to describe a set of [configuration values][1].
 ///
 /// [1]: crate::layers
 pub trait ConfigDescriptor: Sized {
     /// Creates the configuration descriptor.
+    ///
+    /// The implementation generated by the [`make_config!`](crate::make_config) macro calls the
+    /// default value function of each field once, to show the default value in its
+    /// [descriptor](VarDescriptor::default_val_fmt). It isn't called for the fields providing
+    /// the `default_val_fmt` key, which should be done if the function is expensive, nor for the
+    /// secret fields.
     fn define() -> Self;
+
+    #[doc(hidden)]
+    const __VAR_NAMES: &'static [VarNames] = &[];
+
+    /// Walks through the configuration with the provided visitor, without reading the
+    /// environment.
+    ///
+    /// The implementation generated by the [`make_config!`](crate::make_config) macro calls the
+    /// visitor for each field, in their declaration order. The default implementation visits
+    /// nothing.
+    ///
+    /// See [`ConfigVisitor`] for more information.
+    fn visit<'a>(&'a self, visitor: &mut dyn ConfigVisitor<'a>) {
+        let _ = visitor;
+    }
+
+    /// Returns an iterator over the descriptors of all the variables of the configuration,
+    /// including the ones of the nested configurations, in their declaration order.
+    ///
+    /// Unlike [`ConfigInitializer::init_raw`][1], the environment is never read, so no value
+    /// gets [cached][2] in the process.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use mkenv::prelude::*;
+    /// mkenv::make_config! {
+    ///   struct DbConfig {
+    ///     url: { var_name: "DB_URL" },
+    ///   }
+    /// }
+    ///
+    /// mkenv::make_config! {
+    ///   struct AppConfig {
+    ///     port: { var_name: "PORT" },
+    ///     db: { DbConfig },
+    ///   }
+    /// }
+    ///
+    /// let config = AppConfig::define();
+    /// itertools::assert_equal(
+    ///   config.descriptors().map(|descriptor| descriptor.var_name),
+    ///   ["PORT", "DB_URL"],
+    /// );
+    /// ```
+    ///
+    /// [1]: crate::exec::ConfigInitializer::init_raw
+    /// [2]: crate::layers::Cached
+    fn descriptors(&self) -> vec::IntoIter<&VarDescriptor> {
+        struct Collector<'a>(Vec<&'a VarDescriptor>);
+
+        impl<'a> ConfigVisitor<'a> for Collector<'a> {
+            fn visit_var(
+                &mut self,
+                _name: &'static str,
+                descriptor: &'a VarDescriptor,
+                _layers: &LayerDescription,
+            ) {
+                self.0.push(descriptor);
+            }
+        }
+
+        let mut collector = Collector(Vec::new());
+        self.visit(&mut collector);
+        collector.0.into_iter()
+    }
+}
+
+/// Walks through the variables of a configuration, without reading the environment.
+///
+/// It is driven by the [`ConfigDescriptor::visit`] method. For each field of the configuration,
+/// in their declaration order:
+///
+/// * if it is a configuration value, [`visit_var`](Self::visit_var) is called;
+/// * if it is a nested configuration, [`enter_group`](Self::enter_group) is called, then the
+///   nested configuration is visited, then [`exit_group`](Self::exit_group) is called.
+///
+/// The `'a` lifetime is the one of the visited configuration, so the descriptors can be kept
+/// by the visitor.
+///
+/// This is the building block of the [environment documentation](crate::doc::EnvDoc), and can be
+/// used to write custom exporters or linters.
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::{prelude::*, ConfigVisitor, LayerDescription, VarDescriptor};
+/// mkenv::make_config! {
+///   struct DbConfig {
+///     url: { var_name: "DB_URL" },
+///   }
+/// }
+///
+/// mkenv::make_config! {
+///   struct AppConfig {
+///     port: { var_name: "PORT" },
+///     db: { DbConfig },
+///   }
+/// }
+///
+/// /// Collects the path of every variable.
+/// #[derive(Default)]
+/// struct Paths {
+///   current: Vec<&'static str>,
+///   paths: Vec<String>,
+/// }
+///
+/// impl ConfigVisitor<'_> for Paths {
+///   fn enter_group(&mut self, name: &'static str) {
+///     self.current.push(name);
+///   }
+///
+///   fn visit_var(&mut self, name: &'static str, descriptor: &VarDescriptor, _: &LayerDescription) {
+///     let mut path = self.current.clone();
+///     path.push(name);
+///     self.paths.push(format!("{} = {}", path.join("."), descriptor.var_name));
+///   }
+///
+///   fn exit_group(&mut self, _name: &'static str) {
+///     self.current.pop();
+///   }
+/// }
+///
+/// let mut visitor = Paths::default();
+/// AppConfig::define().visit(&mut visitor);
+/// assert_eq!(visitor.paths, ["port = PORT", "db.url = DB_URL"]);
+/// ```
+pub trait ConfigVisitor<'a> {
+    /// Called before visiting the nested configuration of the field with the provided name.
+    ///
+    /// The default implementation does nothing.
+    fn enter_group(&mut self, name: &'static str) {
+        let _ = name;
+    }
+
+    /// Called for the configuration value of the field with the provided name, with its
+    /// descriptor and the description of its layers.
+    fn visit_var(
+        &mut self,
+        name: &'static str,
+        descriptor: &'a VarDescriptor,
+        layers: &LayerDescription,
+    );
+
+    /// Called after visiting the nested configuration of the field with the provided name.
+    ///
+    /// The default implementation does nothing.
+    fn exit_group(&mut self, name: &'static str) {
+        let _ = name;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{prelude::*, tests::with_env};
+
+    use super::{ConfigVisitor, LayerDescription, VarDescriptor, short_type_name};
+
+    #[test]
+    fn assert_short_type_name() {
+        assert_eq!(
+            short_type_name("core::option::Option<alloc::string::String>"),
+            "Option<String>"
+        );
+        assert_eq!(
+            short_type_name("&(u8, core::time::Duration)"),
+            "&(u8, Duration)"
+        );
+    }
+
+    #[test]
+    fn assert_layer_description() {
+        let config = TextVar::from_var_name("__TEST_DESCRIBE_TIMEOUT")
+            .default_fmt_val("5s")
+            .file_read()
+            .parsed::<Duration>(|input| Ok(Duration::from_secs(input.parse()?)))
+            .or_default_val(|| Duration::from_secs(5))
+            .cached();
+
+        let desc = config.describe();
+        itertools::assert_equal(
+            desc.steps(),
+            [
+                "text",
+                "file content",
+                "parse Duration",
+                "default 5s",
+                "cached",
+            ],
+        );
+        assert_eq!(desc.output_type(), "Duration");
+        assert_eq!(
+            desc.to_string(),
+            "text → file content → parse Duration → default 5s → cached"
+        );
+    }
+
+    #[test]
+    fn assert_descriptors_no_read() {
+        crate::make_config! {
+            struct NestedConfig {
+                url: {
+                    var_name: "__TEST_DESCRIPTORS_URL",
+                    description: "The URL",
+                },
+            }
+        }
+
+        crate::make_config! {
+            struct TestConfig {
+                port: {
+                    var_name: "__TEST_DESCRIPTORS_PORT",
+                    layers: [parsed_from_str<u16>(), or_default_val(|| 8080), cached()],
+                },
+                nested: { NestedConfig },
+                host: {
+                    var_name: "__TEST_DESCRIPTORS_HOST",
+                },
+            }
+        }
+
+        let config = TestConfig::define();
+        itertools::assert_equal(
+            config.descriptors().map(|d| (d.var_name, d.description())),
+            [
+                ("__TEST_DESCRIPTORS_PORT", None),
+                ("__TEST_DESCRIPTORS_URL", Some("The URL")),
+                ("__TEST_DESCRIPTORS_HOST", None),
+            ],
+        );
+
+        // The cached value wasn't computed by the traversal
+        let port = with_env([("__TEST_DESCRIPTORS_PORT", "80")], || *config.port.get());
+        assert_eq!(port, 80);
+    }
+
+    #[test]
+    fn assert_visitor_events() {
+        struct Events(Vec<String>);
+
+        impl ConfigVisitor<'_> for Events {
+            fn enter_group(&mut self, name: &'static str) {
+                self.0.push(format!("enter {name}"));
+            }
+
+            fn visit_var(
+                &mut self,
+                name: &'static str,
+                descriptor: &VarDescriptor,
+                layers: &LayerDescription,
+            ) {
+                self.0.push(format!(
+                    "var {name}: {} ({})",
+                    descriptor.var_name,
+                    layers.output_type()
+                ));
+            }
+
+            fn exit_group(&mut self, name: &'static str) {
+                self.0.push(format!("exit {name}"));
+            }
+        }
+
+        crate::make_config! {
+            struct InnerConfig {
+                level: {
+                    var_name: "__TEST_VISIT_LEVEL",
+                    layers: [parsed_from_str<u8>()],
+                },
+            }
+        }
+
+        crate::make_config! {
+            struct OuterConfig {
+                name: {
+                    var_name: "__TEST_VISIT_NAME",
+                },
+                inner: { InnerConfig },
+            }
+        }
+
+        crate::make_config! {
+            struct TestConfig {
+                outer: { OuterConfig },
+                flag: {
+                    var_name: "__TEST_VISIT_FLAG",
+                    layers: [flag_bool()],
+                },
+            }
+        }
+
+        let mut events = Events(Vec::new());
+        TestConfig::define().visit(&mut events);
+        itertools::assert_equal(
+            &events.0,
+            &[
+                "enter outer",
+                "var name: __TEST_VISIT_NAME (String)",
+                "enter inner",
+                "var level: __TEST_VISIT_LEVEL (u8)",
+                "exit inner",
+                "exit outer",
+                "var flag: __TEST_VISIT_FLAG (bool)",
+            ],
+        );
+    }
 }
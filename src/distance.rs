@@ -0,0 +1,92 @@
+//! Similarity of environment variable names, to suggest the intended ones.
+
+/// Returns the edit distance between two names, i.e. the minimal number of inserted, removed,
+/// replaced or swapped adjacent characters to go from one to the other.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    // The distances from the two previous prefixes of `a` to each prefix of `b`
+    let mut before = vec![0; b.len() + 1];
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let replaced = prev[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            row[j] = replaced.min(row[j - 1] + 1).min(prev[j] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(before[j - 2] + 1);
+            }
+        }
+        before = prev;
+        prev = row;
+    }
+
+    prev[b.len()]
+}
+
+/// Returns whether two names are similar, ignoring the case.
+///
+/// The names are compared word by word, the words being separated by underscores. Two words are
+/// similar if they're equal, if one is an abbreviation of the other (e.g. `DB` and `DATABASE`),
+/// or if they're at most one edit apart for every three characters. Names with a different
+/// number of words are only similar if they're one edit apart, e.g. `DBURL` and `DB_URL`.
+pub(crate) fn is_similar(a: &str, b: &str) -> bool {
+    let (a, b) = (a.to_lowercase(), b.to_lowercase());
+    let (a_words, b_words) = (a.split('_'), b.split('_'));
+    if a_words.clone().count() != b_words.clone().count() {
+        return edit_distance(&a, &b) <= 1;
+    }
+    a_words.zip(b_words).all(|(a, b)| is_similar_word(a, b))
+}
+
+fn is_similar_word(a: &str, b: &str) -> bool {
+    let len = a.chars().count().max(b.chars().count());
+    a == b
+        || is_abbreviation(a, b)
+        || is_abbreviation(b, a)
+        || edit_distance(a, b) <= (len / 3).max(1)
+}
+
+/// Returns whether `short` is an abbreviation of `long`, i.e. if it starts with the same
+/// character, and its characters appear in the same order in `long`.
+fn is_abbreviation(short: &str, long: &str) -> bool {
+    let mut long_chars = long.chars();
+    short.chars().count() >= 2
+        && short.chars().next() == long.chars().next()
+        && short.chars().all(|c| long_chars.any(|l| l == c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, is_similar};
+
+    #[test]
+    fn assert_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("DB_URL", "DB_URL"), 0);
+        assert_eq!(edit_distance("DB_URL", ""), 6);
+        assert_eq!(edit_distance("DB_ULR", "DB_URL"), 1);
+        assert_eq!(edit_distance("DB_URL", "DATABASE_URL"), 6);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn assert_similar() {
+        assert!(is_similar("DB_URL", "db_url"));
+        assert!(is_similar("DB_URL", "DATABASE_URL"));
+        assert!(is_similar("DB_URL", "DB_ULR"));
+        assert!(is_similar("DB_URL", "DBURL"));
+        assert!(is_similar("USER", "USERNAME"));
+        assert!(is_similar("DB_POOL", "DB_POLO"));
+    }
+
+    #[test]
+    fn assert_not_similar() {
+        assert!(!is_similar("DB_URL", "PORT"));
+        assert!(!is_similar("PORT", "PWD"));
+        assert!(!is_similar("HOST", "HOME"));
+        assert!(!is_similar("DB_HOST", "DB_PORT"));
+        assert!(!is_similar("REDIS_URL", "DB_URL"));
+        assert!(!is_similar("DB_URL", "DB_URL_FILE"));
+    }
+}
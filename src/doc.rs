@@ -0,0 +1,1056 @@
+//! Contains the generator of the documentation of the environment of a configuration.
+//!
+//! The documentation is built from the [descriptors](crate::VarDescriptor) and the
+//! [layers](crate::LayerDescription) of the configuration values, and never reads the
+//! environment.
+
+use std::{fmt, ops::Bound};
+
+use crate::{
+    descriptor::{
+        ConfigDescriptor, ConfigVisitor, Constraint, LayerDescription, SECRET_PLACEHOLDER,
+        VarDescriptor,
+    },
+    json,
+};
+
+/// The documentation of the environment of a configuration.
+///
+/// The variables are grouped in sections following the nested configurations. It can be rendered
+/// as a [Markdown table](EnvDoc::markdown) or an [HTML fragment](EnvDoc::html), with the name,
+/// the description, the type, the default value, whether it is required or secret, and the
+/// constraints of each variable.
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::{doc::EnvDoc, prelude::*};
+/// mkenv::make_config! {
+///   struct AppConfig {
+///     port: {
+///       var_name: "DOC_PORT",
+///       description: "The port to listen on",
+///       layers: [parsed_from_str<u16>(), or_default_val(|| 8080)],
+///     },
+///   }
+/// }
+///
+/// let doc = EnvDoc::new(&AppConfig::define());
+/// assert_eq!(
+///   doc.markdown().to_string(),
+///   "| Name | Description | Type | Default | Required | Secret | Constraints |\n\
+///    | --- | --- | --- | --- | --- | --- | --- |\n\
+///    | `DOC_PORT` | The port to listen on | `u16` | `8080` | no | no | valid u16 |\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnvDoc {
+    root: DocGroup,
+}
+
+impl EnvDoc {
+    /// Builds the documentation of the provided configuration.
+    pub fn new<C: ConfigDescriptor>(config: &C) -> Self {
+        let mut builder = DocBuilder {
+            stack: vec![DocGroup::default()],
+        };
+        config.visit(&mut builder);
+        Self {
+            root: builder.stack.swap_remove(0),
+        }
+    }
+
+    /// Returns the root section of the documentation.
+    #[inline]
+    pub fn root(&self) -> &DocGroup {
+        &self.root
+    }
+
+    /// Returns the documentation as a Markdown table per section.
+    #[inline]
+    pub fn markdown(&self) -> Markdown<'_> {
+        Markdown { doc: self }
+    }
+
+    /// Returns the documentation as an HTML fragment, with a table per section.
+    #[inline]
+    pub fn html(&self) -> Html<'_> {
+        Html { doc: self }
+    }
+
+    /// Returns the documentation as a commented dotenv template, e.g. for a `.env.example`
+    /// file.
+    ///
+    /// See [`Dotenv`] for the format.
+    #[inline]
+    pub fn dotenv(&self) -> Dotenv<'_> {
+        Dotenv { doc: self }
+    }
+
+    /// Returns the documentation as a JSON Schema (draft 2020-12) of the environment.
+    ///
+    /// See [`JsonSchema`] for the format.
+    #[inline]
+    pub fn json_schema(&self) -> JsonSchema<'_> {
+        JsonSchema { doc: self }
+    }
+
+    /// Returns an iterator over all the variables, in their declaration order.
+    ///
+    /// The variables of the nested configurations come where the configurations are declared,
+    /// which is the same order as [`ConfigDescriptor::descriptors`].
+    pub fn vars(&self) -> impl Iterator<Item = &DocVar> {
+        let mut vars = Vec::new();
+        collect_vars(&self.root, &mut vars);
+        vars.into_iter()
+    }
+}
+
+/// Pushes the variables of the section and its nested sections, in their declaration order.
+fn collect_vars<'a>(group: &'a DocGroup, out: &mut Vec<&'a DocVar>) {
+    let (mut vars, mut groups) = (group.vars.iter(), group.groups.iter());
+    for item in &group.items {
+        match item {
+            DocItem::Var => out.extend(vars.next()),
+            DocItem::Group => {
+                if let Some(group) = groups.next() {
+                    collect_vars(group, out);
+                }
+            }
+        }
+    }
+}
+
+/// A section of the documentation, corresponding to a configuration.
+#[derive(Debug, Clone, Default)]
+pub struct DocGroup {
+    path: Vec<&'static str>,
+    vars: Vec<DocVar>,
+    groups: Vec<DocGroup>,
+    // The kinds of the declared fields, in their order, to interleave the variables and the groups
+    items: Vec<DocItem>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DocItem {
+    Var,
+    Group,
+}
+
+impl DocGroup {
+    /// Returns the path of the section, i.e. the names of the fields of the nested
+    /// configurations. It is empty for the root section.
+    #[inline]
+    pub fn path(&self) -> &[&'static str] {
+        &self.path
+    }
+
+    /// Returns the variables declared directly in this section.
+    #[inline]
+    pub fn vars(&self) -> &[DocVar] {
+        &self.vars
+    }
+
+    /// Returns the sections of the nested configurations.
+    #[inline]
+    pub fn groups(&self) -> &[DocGroup] {
+        &self.groups
+    }
+}
+
+/// The documentation of a single variable.
+#[derive(Debug, Clone)]
+pub struct DocVar {
+    path: Vec<&'static str>,
+    descriptor: VarDescriptor,
+    layers: LayerDescription,
+}
+
+impl DocVar {
+    /// Returns the path of the variable in the configuration.
+    #[inline]
+    pub fn path(&self) -> &[&'static str] {
+        &self.path
+    }
+
+    /// Returns the descriptor of the variable.
+    #[inline]
+    pub fn descriptor(&self) -> &VarDescriptor {
+        &self.descriptor
+    }
+
+    /// Returns the description of the layers of the variable.
+    #[inline]
+    pub fn layers(&self) -> &LayerDescription {
+        &self.layers
+    }
+
+    /// Returns the name of the type of the variable.
+    ///
+    /// It is the [type name](VarDescriptor::type_name) of the descriptor if specified, or the
+    /// output type of the layers otherwise.
+    pub fn type_name(&self) -> &str {
+        self.descriptor
+            .type_name()
+            .unwrap_or(self.layers.output_type())
+    }
+
+    /// Returns the text of the default value of the variable, unless it is secret.
+    ///
+    /// The default value of a secret variable is never shown in the documentation.
+    #[inline]
+    pub fn default_val_fmt(&self) -> Option<&str> {
+        self.descriptor
+            .default_val_fmt()
+            .filter(|_| !self.descriptor.is_secret())
+    }
+
+    /// Returns whether the variable must be set.
+    ///
+    /// It is `false` if the descriptor says so, or if the layers don't fail when it isn't set,
+    /// e.g. because of a default value.
+    #[inline]
+    pub fn is_required(&self) -> bool {
+        self.descriptor.is_required() && !self.layers.is_optional()
+    }
+}
+
+struct DocBuilder {
+    stack: Vec<DocGroup>,
+}
+
+impl DocBuilder {
+    fn current(&mut self) -> &mut DocGroup {
+        self.stack
+            .last_mut()
+            .expect("the root group is never removed")
+    }
+}
+
+impl ConfigVisitor<'_> for DocBuilder {
+    fn enter_group(&mut self, name: &'static str) {
+        let mut path = self.current().path.clone();
+        path.push(name);
+        self.stack.push(DocGroup {
+            path,
+            ..Default::default()
+        });
+    }
+
+    fn visit_var(
+        &mut self,
+        name: &'static str,
+        descriptor: &VarDescriptor,
+        layers: &LayerDescription,
+    ) {
+        let group = self.current();
+        let mut path = group.path.clone();
+        path.push(name);
+        group.vars.push(DocVar {
+            path,
+            descriptor: descriptor.clone(),
+            layers: layers.clone(),
+        });
+        group.items.push(DocItem::Var);
+    }
+
+    fn exit_group(&mut self, _name: &'static str) {
+        if let Some(group) = self.stack.pop() {
+            let parent = self.current();
+            parent.groups.push(group);
+            parent.items.push(DocItem::Group);
+        }
+    }
+}
+
+const COLUMNS: [&str; 7] = [
+    "Name",
+    "Description",
+    "Type",
+    "Default",
+    "Required",
+    "Secret",
+    "Constraints",
+];
+
+fn yes_no(b: bool) -> &'static str {
+    if b { "yes" } else { "no" }
+}
+
+/// Returns the text of the default value shown in the tables, redacted for secret variables.
+fn displayed_default(var: &DocVar) -> Option<&str> {
+    match var.descriptor().default_val_fmt() {
+        Some(_) if var.descriptor().is_secret() => Some(SECRET_PLACEHOLDER),
+        default_val => default_val,
+    }
+}
+
+/// Calls the function on each section that has variables, in depth-first order.
+fn for_each_section<F>(group: &DocGroup, f: &mut F) -> fmt::Result
+where
+    F: FnMut(&DocGroup) -> fmt::Result,
+{
+    if !group.vars.is_empty() {
+        f(group)?;
+    }
+    group
+        .groups
+        .iter()
+        .try_for_each(|group| for_each_section(group, f))
+}
+
+fn heading_level(group: &DocGroup) -> usize {
+    (group.path.len() + 1).min(6)
+}
+
+/// The documentation rendered as Markdown.
+///
+/// See [`EnvDoc::markdown`].
+pub struct Markdown<'a> {
+    doc: &'a EnvDoc,
+}
+
+/// Writes the text of a Markdown table cell.
+fn write_md_cell(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            '|' => f.write_str("\\|")?,
+            '\n' => f.write_str(" ")?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    Ok(())
+}
+
+fn write_md_code(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("`")?;
+    write_md_cell(f, s)?;
+    f.write_str("`")
+}
+
+fn fmt_md_row(var: &DocVar, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let descriptor = var.descriptor();
+
+    f.write_str("| ")?;
+    write_md_code(f, descriptor.var_name)?;
+    f.write_str(" | ")?;
+    let mut sep = "";
+    if let Some(description) = descriptor.description() {
+        write_md_cell(f, description)?;
+        sep = " ";
+    }
+    if let Some(example) = descriptor.example() {
+        write!(f, "{sep}Example: ")?;
+        write_md_code(f, example)?;
+        sep = " ";
+    }
+    if let Some(note) = descriptor.deprecated() {
+        write!(f, "{sep}**Deprecated**: ")?;
+        write_md_cell(f, note)?;
+    }
+    f.write_str(" | ")?;
+    write_md_code(f, var.type_name())?;
+    f.write_str(" | ")?;
+    if let Some(default_val) = displayed_default(var) {
+        write_md_code(f, default_val)?;
+    }
+    write!(
+        f,
+        " | {} | {} | ",
+        yes_no(var.is_required()),
+        yes_no(descriptor.is_secret())
+    )?;
+    for (i, constraint) in var.layers().constraints().iter().enumerate() {
+        if i > 0 {
+            f.write_str("; ")?;
+        }
+        write_md_cell(f, &constraint.to_string())?;
+    }
+    writeln!(f, " |")
+}
+
+impl fmt::Display for Markdown<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for_each_section(&self.doc.root, &mut |group| {
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+
+            if !group.path.is_empty() {
+                writeln!(
+                    f,
+                    "{} `{}`\n",
+                    "#".repeat(heading_level(group)),
+                    group.path.join(".")
+                )?;
+            }
+
+            writeln!(f, "| {} |", COLUMNS.join(" | "))?;
+            writeln!(f, "|{}", " --- |".repeat(COLUMNS.len()))?;
+            group.vars.iter().try_for_each(|var| fmt_md_row(var, f))
+        })
+    }
+}
+
+/// The documentation rendered as an HTML fragment.
+///
+/// See [`EnvDoc::html`].
+pub struct Html<'a> {
+    doc: &'a EnvDoc,
+}
+
+fn write_html(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            '&' => f.write_str("&amp;")?,
+            '<' => f.write_str("&lt;")?,
+            '>' => f.write_str("&gt;")?,
+            '"' => f.write_str("&quot;")?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    Ok(())
+}
+
+fn write_html_code(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("<code>")?;
+    write_html(f, s)?;
+    f.write_str("</code>")
+}
+
+fn fmt_html_row(var: &DocVar, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let descriptor = var.descriptor();
+
+    f.write_str("    <tr><td>")?;
+    write_html_code(f, descriptor.var_name)?;
+    f.write_str("</td><td>")?;
+    let mut sep = "";
+    if let Some(description) = descriptor.description() {
+        write_html(f, description)?;
+        sep = " ";
+    }
+    if let Some(example) = descriptor.example() {
+        write!(f, "{sep}Example: ")?;
+        write_html_code(f, example)?;
+        sep = " ";
+    }
+    if let Some(note) = descriptor.deprecated() {
+        write!(f, "{sep}<strong>Deprecated</strong>: ")?;
+        write_html(f, note)?;
+    }
+    f.write_str("</td><td>")?;
+    write_html_code(f, var.type_name())?;
+    f.write_str("</td><td>")?;
+    if let Some(default_val) = displayed_default(var) {
+        write_html_code(f, default_val)?;
+    }
+    write!(
+        f,
+        "</td><td>{}</td><td>{}</td><td>",
+        yes_no(var.is_required()),
+        yes_no(descriptor.is_secret())
+    )?;
+    for (i, constraint) in var.layers().constraints().iter().enumerate() {
+        if i > 0 {
+            f.write_str("; ")?;
+        }
+        write_html(f, &constraint.to_string())?;
+    }
+    writeln!(f, "</td></tr>")
+}
+
+impl fmt::Display for Html<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for_each_section(&self.doc.root, &mut |group| {
+            if !group.path.is_empty() {
+                let level = heading_level(group);
+                write!(f, "<h{level}>")?;
+                write_html_code(f, &group.path.join("."))?;
+                writeln!(f, "</h{level}>")?;
+            }
+
+            writeln!(f, "<table>\n  <thead>\n    <tr>")?;
+            for column in COLUMNS {
+                writeln!(f, "      <th>{column}</th>")?;
+            }
+            writeln!(f, "    </tr>\n  </thead>\n  <tbody>")?;
+            group.vars.iter().try_for_each(|var| fmt_html_row(var, f))?;
+            writeln!(f, "  </tbody>\n</table>")
+        })
+    }
+}
+
+/// The documentation rendered as a commented dotenv template.
+///
+/// Each variable is preceded by its description, its deprecation note and its example value as
+/// comments. Its value is:
+///
+/// - a `<secret>` placeholder if it is secret, commented out if it has a default value;
+/// - otherwise, its default value, commented out, if it has one;
+/// - otherwise, its example value if it has one;
+/// - otherwise, an empty value.
+///
+/// The variables that aren't required are commented out. The nested configurations are
+/// introduced by a comment with their path.
+///
+/// The output only depends on the configuration, so it can be compared with a checked-in file
+/// in a test:
+///
+/// ```no_run
+/// # use mkenv::{doc::EnvDoc, prelude::*};
+/// # mkenv::make_config! { struct AppConfig { user: { var_name: "USER" } } }
+/// let doc = EnvDoc::new(&AppConfig::define());
+/// let checked_in = std::fs::read_to_string(".env.example").unwrap();
+/// assert_eq!(doc.dotenv().to_string(), checked_in);
+/// ```
+///
+/// See [`EnvDoc::dotenv`].
+pub struct Dotenv<'a> {
+    doc: &'a EnvDoc,
+}
+
+/// Writes a dotenv value, quoted if needed.
+fn write_dotenv_value(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    let needs_quotes = s
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, '#' | '"' | '\'' | '\\' | '$' | '`'));
+    if !needs_quotes {
+        return f.write_str(s);
+    }
+
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' | '\\' | '$' | '`' => write!(f, "\\{c}")?,
+            '\n' => f.write_str("\\n")?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    f.write_str("\"")
+}
+
+fn write_comment_lines(f: &mut fmt::Formatter<'_>, prefix: &str, s: &str) -> fmt::Result {
+    for line in s.lines() {
+        writeln!(f, "# {prefix}{line}")?;
+    }
+    Ok(())
+}
+
+fn fmt_dotenv_var(var: &DocVar, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let descriptor = var.descriptor();
+
+    if let Some(description) = descriptor.description() {
+        write_comment_lines(f, "", description)?;
+    }
+    if let Some(note) = descriptor.deprecated() {
+        write_comment_lines(f, "Deprecated: ", note)?;
+    }
+
+    // The default value of a secret variable must never end up in a checked-in file
+    let has_default = descriptor.default_val_fmt().is_some();
+    let (value, commented) = match (var.default_val_fmt(), descriptor.example()) {
+        _ if descriptor.is_secret() => (SECRET_PLACEHOLDER, has_default || !var.is_required()),
+        (Some(default_val), example) => {
+            if let Some(example) = example {
+                write_comment_lines(f, "Example: ", example)?;
+            }
+            (default_val, true)
+        }
+        (None, example) => (example.unwrap_or_default(), !var.is_required()),
+    };
+
+    if commented {
+        f.write_str("# ")?;
+    }
+    write!(f, "{}=", descriptor.var_name)?;
+    write_dotenv_value(f, value)?;
+    writeln!(f)
+}
+
+impl fmt::Display for Dotenv<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for_each_section(&self.doc.root, &mut |group| {
+            if !group.path.is_empty() {
+                if !first {
+                    writeln!(f)?;
+                }
+                writeln!(f, "# [{}]", group.path.join("."))?;
+                first = false;
+            }
+
+            group.vars.iter().try_for_each(|var| {
+                if !first {
+                    writeln!(f)?;
+                }
+                first = false;
+                fmt_dotenv_var(var, f)
+            })
+        })
+    }
+}
+
+/// The documentation rendered as a JSON Schema (draft 2020-12).
+///
+/// The schema describes an object with a property per environment variable, e.g.:
+///
+/// ```json
+/// {
+///   "$schema": "https://json-schema.org/draft/2020-12/schema",
+///   "type": "object",
+///   "properties": {
+///     "PORT": {
+///       "type": "integer",
+///       "description": "The port to listen on",
+///       "default": 8080,
+///       "minimum": 1,
+///       "maximum": 65535
+///     },
+///     "LOG_FORMAT": {
+///       "type": "string",
+///       "enum": ["json", "text"]
+///     }
+///   },
+///   "required": ["LOG_FORMAT"]
+/// }
+/// ```
+///
+/// * `type` is inferred from the output type of the layers: `integer`, `number` or `boolean` for
+///   the primitive types, and `string` otherwise, e.g. for [byte sizes][1] or [files][2].
+/// * `enum` comes from the [`Choice`][3] layers, and `minimum`, `maximum`,
+///   `exclusiveMinimum` and `exclusiveMaximum` from the [`InRange`][4] layers.
+/// * `description`, `default`, `examples`, `deprecated` and `writeOnly` (for secrets) come from
+///   the [descriptor](VarDescriptor), if provided. The default value of the secrets is omitted.
+/// * `required` lists the [required](DocVar::is_required) variables.
+///
+/// The output is minified, without whitespace.
+///
+/// See [`EnvDoc::json_schema`].
+///
+/// [1]: crate::layers::ByteSize
+/// [2]: crate::layers::FileRead
+/// [3]: crate::layers::Choice
+/// [4]: crate::layers::InRange
+pub struct JsonSchema<'a> {
+    doc: &'a EnvDoc,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum JsonType {
+    Integer,
+    Number,
+    Boolean,
+    String,
+}
+
+impl JsonType {
+    fn of(var: &DocVar) -> Self {
+        let has_text_format = var
+            .layers()
+            .constraints()
+            .iter()
+            .any(|c| matches!(c, Constraint::ByteSize | Constraint::FilePath));
+        if has_text_format {
+            return Self::String;
+        }
+
+        let type_name = var.layers().output_type();
+        let type_name = type_name
+            .strip_prefix("Option<")
+            .and_then(|s| s.strip_suffix('>'))
+            .unwrap_or(type_name);
+        match type_name {
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+            | "i128" | "isize" => Self::Integer,
+            "f32" | "f64" => Self::Number,
+            "bool" => Self::Boolean,
+            _ => Self::String,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::String => "string",
+        }
+    }
+
+    /// Returns whether the text is a valid JSON literal of this type.
+    fn accepts(self, s: &str) -> bool {
+        match self {
+            Self::Integer => is_json_number(s, false),
+            Self::Number => is_json_number(s, true),
+            Self::Boolean => matches!(s, "true" | "false"),
+            Self::String => false,
+        }
+    }
+}
+
+/// Returns whether the text is a JSON number, with a fraction and an exponent if allowed.
+fn is_json_number(s: &str, allow_fraction: bool) -> bool {
+    fn digits(s: &str) -> (&str, &str) {
+        let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        s.split_at(end)
+    }
+
+    let s = s.strip_prefix('-').unwrap_or(s);
+    let (int, mut rest) = digits(s);
+    if int.is_empty() || (int.len() > 1 && int.starts_with('0')) {
+        return false;
+    }
+    if !allow_fraction {
+        return rest.is_empty();
+    }
+
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let (fraction, after) = digits(fraction);
+        if fraction.is_empty() {
+            return false;
+        }
+        rest = after;
+    }
+    if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        let (exponent, after) = digits(exponent);
+        if exponent.is_empty() {
+            return false;
+        }
+        rest = after;
+    }
+    rest.is_empty()
+}
+
+/// Writes the text as a JSON value of the provided type, or as a JSON string if it isn't one.
+fn write_json_value(f: &mut fmt::Formatter<'_>, ty: JsonType, s: &str) -> fmt::Result {
+    if ty.accepts(s) {
+        f.write_str(s)
+    } else {
+        json::write_str(f, s)
+    }
+}
+
+fn fmt_json_bound(
+    f: &mut fmt::Formatter<'_>,
+    bound: &Bound<String>,
+    keys: (&str, &str),
+) -> fmt::Result {
+    let (key, value) = match bound {
+        Bound::Included(value) => (keys.0, value),
+        Bound::Excluded(value) => (keys.1, value),
+        Bound::Unbounded => return Ok(()),
+    };
+    // The bounds are only meaningful for numbers
+    if JsonType::Number.accepts(value) {
+        write!(f, ",\"{key}\":{value}")?;
+    }
+    Ok(())
+}
+
+fn fmt_json_property(var: &DocVar, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let descriptor = var.descriptor();
+    let ty = JsonType::of(var);
+
+    json::write_str(f, descriptor.var_name)?;
+    write!(f, ":{{\"type\":\"{}\"", ty.as_str())?;
+
+    if let Some(description) = descriptor.description() {
+        f.write_str(",\"description\":")?;
+        json::write_str(f, description)?;
+    }
+    if let Some(default_val) = var.default_val_fmt() {
+        f.write_str(",\"default\":")?;
+        write_json_value(f, ty, default_val)?;
+    }
+    if let Some(example) = descriptor.example() {
+        f.write_str(",\"examples\":[")?;
+        write_json_value(f, ty, example)?;
+        f.write_str("]")?;
+    }
+    if descriptor.deprecated().is_some() {
+        f.write_str(",\"deprecated\":true")?;
+    }
+    if descriptor.is_secret() {
+        f.write_str(",\"writeOnly\":true")?;
+    }
+
+    for constraint in var.layers().constraints() {
+        match constraint {
+            Constraint::OneOf { values } => {
+                f.write_str(",\"enum\":[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_json_value(f, ty, value)?;
+                }
+                f.write_str("]")?;
+            }
+            Constraint::Range { start, end } => {
+                fmt_json_bound(f, start, ("minimum", "exclusiveMinimum"))?;
+                fmt_json_bound(f, end, ("maximum", "exclusiveMaximum"))?;
+            }
+            _ => {}
+        }
+    }
+
+    f.write_str("}")
+}
+
+impl fmt::Display for JsonSchema<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            "{\"$schema\":\"https://json-schema.org/draft/2020-12/schema\",\
+             \"type\":\"object\",\"properties\":{",
+        )?;
+        for (i, var) in self.doc.vars().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            fmt_json_property(var, f)?;
+        }
+
+        f.write_str("},\"required\":[")?;
+        let required = self.doc.vars().filter(|var| var.is_required());
+        for (i, var) in required.enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            json::write_str(f, var.descriptor().var_name)?;
+        }
+        f.write_str("]}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{doc::EnvDoc, prelude::*};
+
+    crate::make_config! {
+        struct DbConfig {
+            url: {
+                var_name: "__TEST_DOC_DB_URL",
+                description: "The URL of the database",
+                example: "postgres://localhost/app",
+                secret: true,
+            },
+            pool: {
+                var_name: "__TEST_DOC_DB_POOL",
+                layers: [parsed_from_str<u8>(), or_default_val(|| 4)],
+            },
+        }
+    }
+
+    crate::make_config! {
+        struct AppConfig {
+            db: { DbConfig },
+            verbose: {
+                var_name: "__TEST_DOC_VERBOSE",
+                description: "Logs <everything> | nothing",
+                layers: [flag_present()],
+            },
+            certs: {
+                var_name: "__TEST_DOC_CERTS",
+                layers: [file_read(), optional()],
+                deprecated: "use __TEST_DOC_TLS",
+            },
+        }
+    }
+
+    #[test]
+    fn assert_markdown() {
+        let doc = EnvDoc::new(&AppConfig::define());
+
+        assert_eq!(
+            doc.markdown().to_string(),
+            "| Name | Description | Type | Default | Required | Secret | Constraints |\n\
+             | --- | --- | --- | --- | --- | --- | --- |\n\
+             | `__TEST_DOC_VERBOSE` | Logs <everything> \\| nothing | `bool` |  | no | no |  |\n\
+             | `__TEST_DOC_CERTS` | **Deprecated**: use __TEST_DOC_TLS | `Option<String>` \
+             |  | no | no | path to a readable file |\n\
+             \n\
+             ## `db`\n\
+             \n\
+             | Name | Description | Type | Default | Required | Secret | Constraints |\n\
+             | --- | --- | --- | --- | --- | --- | --- |\n\
+             | `__TEST_DOC_DB_URL` | The URL of the database Example: \
+             `postgres://localhost/app` | `String` |  | yes | yes |  |\n\
+             | `__TEST_DOC_DB_POOL` |  | `u8` | `4` | no | no | valid u8 |\n"
+        );
+    }
+
+    #[test]
+    fn assert_html() {
+        let doc = EnvDoc::new(&AppConfig::define());
+        let html = doc.html().to_string();
+
+        assert!(html.starts_with("<table>\n  <thead>\n    <tr>\n      <th>Name</th>\n"));
+        assert!(html.contains(
+            "    <tr><td><code>__TEST_DOC_VERBOSE</code></td>\
+             <td>Logs &lt;everything&gt; | nothing</td><td><code>bool</code></td>\
+             <td></td><td>no</td><td>no</td><td></td></tr>\n"
+        ));
+        assert!(html.contains("</table>\n<h2><code>db</code></h2>\n<table>\n"));
+        assert!(html.contains(
+            "<td><code>__TEST_DOC_DB_POOL</code></td><td></td><td><code>u8</code></td>\
+             <td><code>4</code></td><td>no</td><td>no</td><td>valid u8</td></tr>\n"
+        ));
+    }
+
+    #[test]
+    fn assert_dotenv() {
+        crate::make_config! {
+            struct TokenConfig {
+                token: {
+                    var_name: "__TEST_DOTENV_TOKEN",
+                    description: "The API token",
+                    secret: true,
+                },
+                greeting: {
+                    var_name: "__TEST_DOTENV_GREETING",
+                    example: "hello there",
+                    layers: [or_default_val(|| "hi".to_owned())],
+                },
+            }
+        }
+
+        crate::make_config! {
+            struct DotenvConfig {
+                name: {
+                    var_name: "__TEST_DOTENV_NAME",
+                    description: "The name\nof the app",
+                    example: "my-app",
+                },
+                tokens: { TokenConfig },
+                level: {
+                    var_name: "__TEST_DOTENV_LEVEL",
+                    layers: [optional()],
+                    deprecated: "use __TEST_DOTENV_LOG",
+                },
+            }
+        }
+
+        let doc = EnvDoc::new(&DotenvConfig::define());
+        let expected = "# The name\n\
+                        # of the app\n\
+                        __TEST_DOTENV_NAME=my-app\n\
+                        \n\
+                        # Deprecated: use __TEST_DOTENV_LOG\n\
+                        # __TEST_DOTENV_LEVEL=\n\
+                        \n\
+                        # [tokens]\n\
+                        \n\
+                        # The API token\n\
+                        __TEST_DOTENV_TOKEN=<secret>\n\
+                        \n\
+                        # Example: hello there\n\
+                        # __TEST_DOTENV_GREETING=hi\n";
+
+        assert_eq!(doc.dotenv().to_string(), expected);
+        // The output only depends on the configuration
+        let doc = EnvDoc::new(&DotenvConfig::define());
+        assert_eq!(doc.dotenv().to_string(), expected);
+    }
+
+    #[test]
+    fn assert_secret_default_hidden() {
+        crate::make_config! {
+            struct SecretConfig {
+                key: {
+                    var_name: "__TEST_DOC_SECRET_KEY",
+                    secret: true,
+                    default_val_fmt: "hunter2",
+                    layers: [or_default_val(|| "hunter2".to_owned())],
+                },
+            }
+        }
+
+        let doc = EnvDoc::new(&SecretConfig::define());
+        let var = doc.vars().next().unwrap();
+        assert_eq!(var.descriptor().default_val_fmt(), Some("hunter2"));
+        assert_eq!(var.default_val_fmt(), None);
+
+        let markdown = doc.markdown().to_string();
+        assert!(markdown.contains("| `String` | `<secret>` | no | yes |"));
+        let html = doc.html().to_string();
+        assert!(html.contains("<td><code>&lt;secret&gt;</code></td>"));
+        let dotenv = doc.dotenv().to_string();
+        assert_eq!(dotenv, "# __TEST_DOC_SECRET_KEY=<secret>\n");
+        let schema = doc.json_schema().to_string();
+        assert!(schema.contains(r#""__TEST_DOC_SECRET_KEY":{"type":"string","writeOnly":true}"#));
+
+        for out in [markdown, html, dotenv, schema] {
+            assert!(!out.contains("hunter2"));
+        }
+    }
+
+    #[test]
+    fn assert_json_schema() {
+        crate::make_config! {
+            struct SchemaConfig {
+                port: {
+                    var_name: "__TEST_SCHEMA_PORT",
+                    description: "The \"port\"",
+                    layers: [parsed_from_str<u16>(), in_range(1..=65535), or_default_val(|| 8080)],
+                },
+                format: {
+                    var_name: "__TEST_SCHEMA_FORMAT",
+                    layers: [one_of(&["json", "text"])],
+                    example: "json",
+                },
+                ratio: {
+                    var_name: "__TEST_SCHEMA_RATIO",
+                    layers: [parsed_from_str<f64>(), in_range(0.0..1.0), optional()],
+                    deprecated: "unused",
+                },
+                db: { DbConfig },
+                cache: {
+                    var_name: "__TEST_SCHEMA_CACHE",
+                    layers: [byte_size(), or_default_val(|| 1024)],
+                    default_val_fmt: "1KiB",
+                },
+            }
+        }
+
+        let config = SchemaConfig::define();
+        let doc = EnvDoc::new(&config);
+        itertools::assert_equal(
+            doc.vars().map(|var| var.descriptor().var_name),
+            config.descriptors().map(|descriptor| descriptor.var_name),
+        );
+        assert_eq!(
+            doc.json_schema().to_string(),
+            concat!(
+                r#"{"$schema":"https://json-schema.org/draft/2020-12/schema","type":"object","#,
+                r#""properties":{"#,
+                r#""__TEST_SCHEMA_PORT":{"type":"integer","description":"The \"port\"","#,
+                r#""default":8080,"minimum":1,"maximum":65535},"#,
+                r#""__TEST_SCHEMA_FORMAT":{"type":"string","examples":["json"],"#,
+                r#""enum":["json","text"]},"#,
+                r#""__TEST_SCHEMA_RATIO":{"type":"number","deprecated":true,"#,
+                r#""minimum":0,"exclusiveMaximum":1},"#,
+                r#""__TEST_DOC_DB_URL":{"type":"string","description":"The URL of the database","#,
+                r#""examples":["postgres://localhost/app"],"writeOnly":true},"#,
+                r#""__TEST_DOC_DB_POOL":{"type":"integer","default":4},"#,
+                r#""__TEST_SCHEMA_CACHE":{"type":"string","default":"1KiB"}"#,
+                r#"},"required":["__TEST_SCHEMA_FORMAT","__TEST_DOC_DB_URL"]}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn assert_sections() {
+        let doc = EnvDoc::new(&AppConfig::define());
+
+        let db = &doc.root().groups()[0];
+        assert_eq!(db.path(), ["db"]);
+        itertools::assert_equal(
+            db.vars().iter().map(|var| var.path()),
+            [["db", "url"], ["db", "pool"]],
+        );
+        assert!(doc.root().vars().iter().all(|var| !var.is_required()));
+    }
+}
@@ -1,17 +1,42 @@
 //! Contains all error types.
 
-use std::{env::VarError, error::Error, fmt};
+use std::{
+    convert::Infallible,
+    env::VarError,
+    error::Error,
+    fmt, io,
+    ops::Bound,
+    process::{ExitCode, Termination},
+};
 
-use crate::exec::FmtExecResults;
+use crate::{
+    exec::{FmtExecResults, JsonExecResults},
+    layers::in_range::fmt_bounds,
+    report::{self, DisplayReport, InitReport, ReportFormatter},
+};
 
 /// Generic error when reading an environment variable.
+///
+/// The `E` type parameter is the type of the other errors than the ones related to the
+/// environment variable itself. By default, they are type-erased, but some layers, such as
+/// [`TypedParsed`][1], keep their concrete type, so it can be matched on without downcasting.
+///
+/// [1]: crate::layers::TypedParsed
 #[derive(Debug)]
-pub enum ReadVarError {
+pub enum ReadVarError<E: ?Sized = dyn Error + Send + Sync> {
     Var(VarError),
-    Other(Box<dyn Error>),
+    Other(Box<E>),
 }
 
-impl PartialEq for ReadVarError {
+impl ReadVarError {
+    /// Creates a type-erased error from the provided error.
+    #[inline]
+    pub fn other<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        Self::Other(Box::new(error))
+    }
+}
+
+impl<E: ?Sized> PartialEq for ReadVarError<E> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Var(e1), Self::Var(e2)) => e1 == e2,
@@ -21,7 +46,7 @@ impl PartialEq for ReadVarError {
     }
 }
 
-impl fmt::Display for ReadVarError {
+impl<E: ?Sized + fmt::Display> fmt::Display for ReadVarError<E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ReadVarError::Var(var_error) => fmt::Display::fmt(var_error, f),
@@ -30,19 +55,156 @@ impl fmt::Display for ReadVarError {
     }
 }
 
-impl Error for ReadVarError {
+impl<E: ?Sized + AsDynError + fmt::Debug + fmt::Display> Error for ReadVarError<E> {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             ReadVarError::Var(var_error) => Some(var_error),
-            ReadVarError::Other(error) => Some(&**error),
+            ReadVarError::Other(error) => Some(error.as_dyn_error()),
+        }
+    }
+}
+
+/// Represents error types that can be viewed as a `dyn Error` trait object.
+///
+/// It is implemented for every sized error type, and for the `dyn Error` trait objects
+/// themselves.
+pub trait AsDynError {
+    /// Returns the error as a trait object.
+    fn as_dyn_error(&self) -> &(dyn Error + 'static);
+}
+
+impl<E: Error + 'static> AsDynError for E {
+    #[inline(always)]
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for dyn Error {
+    #[inline(always)]
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for dyn Error + Send + Sync {
+    #[inline(always)]
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+/// The kind of an error when reading a configuration value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The environment variable isn't set.
+    NotPresent,
+    /// The environment variable isn't valid unicode.
+    NotUnicode,
+    /// The value couldn't be parsed.
+    Parse,
+    /// An I/O error occurred, e.g. when reading a file.
+    Io,
+    /// The value is well-formed, but isn't allowed, e.g. because it is out of range.
+    Invalid,
+    /// The environment variable is already declared by another field of the configuration.
+    Duplicate,
+    /// Any other error.
+    Other,
+}
+
+impl ErrorKind {
+    /// Returns the kind of the provided error, by inspecting it and its sources.
+    pub fn of(error: &(dyn Error + 'static)) -> Self {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(var_error) = error.downcast_ref::<VarError>() {
+                return Self::from(var_error);
+            }
+            if error.is::<ParseError>()
+                || error.is::<ByteSizeError>()
+                || error.is::<FlagBoolError>()
+            {
+                return Self::Parse;
+            }
+            if error.is::<io::Error>() {
+                return Self::Io;
+            }
+            if error.is::<ChoiceError>() || error.is::<RangeError>() {
+                return Self::Invalid;
+            }
+            if error.is::<DuplicateVarError>() {
+                return Self::Duplicate;
+            }
+            current = error.source();
         }
+        Self::Other
+    }
+
+    /// Returns the snake case name of the kind, e.g. `not_present`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::NotPresent => "not_present",
+            ErrorKind::NotUnicode => "not_unicode",
+            ErrorKind::Parse => "parse",
+            ErrorKind::Io => "io",
+            ErrorKind::Invalid => "invalid",
+            ErrorKind::Duplicate => "duplicate",
+            ErrorKind::Other => "other",
+        }
+    }
+}
+
+impl From<&VarError> for ErrorKind {
+    fn from(value: &VarError) -> Self {
+        match value {
+            VarError::NotPresent => Self::NotPresent,
+            VarError::NotUnicode(_) => Self::NotUnicode,
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Represents errors returned by layers, that can be classified into an [`ErrorKind`].
+pub trait LayerError {
+    /// Returns the kind of the error.
+    fn kind(&self) -> ErrorKind;
+}
+
+impl<E: ?Sized + AsDynError> LayerError for ReadVarError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            ReadVarError::Var(var_error) => ErrorKind::from(var_error),
+            ReadVarError::Other(error) => ErrorKind::of(error.as_dyn_error()),
+        }
+    }
+}
+
+impl<E: LayerError> LayerError for CachedError<'_, E> {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        self.0.kind()
+    }
+}
+
+impl LayerError for Infallible {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match *self {}
     }
 }
 
 /// An error during the parsing of a configuration value.
 #[derive(Debug)]
 pub struct ParseError {
-    pub(crate) source: Box<dyn Error>,
+    pub(crate) source: Box<dyn Error + Send + Sync>,
 }
 
 impl fmt::Display for ParseError {
@@ -59,6 +221,155 @@ impl Error for ParseError {
     }
 }
 
+/// An error during the parsing of a size in bytes, with the [`ByteSize`][1] layer.
+///
+/// The value itself isn't part of the error, so it can't leak if the variable is secret.
+///
+/// [1]: crate::layers::ByteSize
+#[derive(Debug, PartialEq)]
+pub enum ByteSizeError {
+    /// The numeric part of the size is invalid.
+    InvalidNumber,
+    /// The unit suffix isn't recognized.
+    UnknownUnit,
+    /// The size doesn't fit in a `u64`.
+    Overflow,
+}
+
+impl fmt::Display for ByteSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ByteSizeError::InvalidNumber => f.write_str("invalid byte size number"),
+            ByteSizeError::UnknownUnit => {
+                f.write_str("unknown byte size unit (expected e.g. `B`, `kB`, `MB`, `KiB`, `MiB`)")
+            }
+            ByteSizeError::Overflow => write!(f, "byte size overflows {} bytes", u64::MAX),
+        }
+    }
+}
+
+impl Error for ByteSizeError {}
+
+/// An error during the parsing of a boolean flag, with the [`FlagBool`][1] layer.
+///
+/// The value itself isn't part of the error, so it can't leak if the variable is secret.
+///
+/// [1]: crate::layers::FlagBool
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub struct FlagBoolError;
+
+impl fmt::Display for FlagBoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::layers::flag_bool::{FALSY_VALUES, TRUTHY_VALUES};
+
+        let fmt_values = |f: &mut fmt::Formatter<'_>, values: &[&str]| {
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "`{value}`")?;
+            }
+            Ok(())
+        };
+
+        f.write_str("invalid boolean value (expected one of ")?;
+        fmt_values(f, TRUTHY_VALUES)?;
+        f.write_str(" for true, or ")?;
+        fmt_values(f, FALSY_VALUES)?;
+        f.write_str(" for false)")
+    }
+}
+
+impl Error for FlagBoolError {}
+
+/// An error when the value isn't one of the allowed values, with the [`Choice`][1] layer.
+///
+/// The value itself isn't part of the error, so it can't leak if the variable is secret.
+///
+/// [1]: crate::layers::Choice
+#[derive(Debug, PartialEq)]
+pub struct ChoiceError {
+    pub(crate) choices: &'static [&'static str],
+}
+
+impl fmt::Display for ChoiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid value (expected one of ")?;
+        for (i, choice) in self.choices.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{choice}`")?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl Error for ChoiceError {}
+
+/// An error when the value is out of the allowed range, with the [`InRange`][1] layer.
+///
+/// The value itself isn't part of the error, so it can't leak if the variable is secret.
+///
+/// [1]: crate::layers::InRange
+#[derive(Debug, PartialEq)]
+pub struct RangeError {
+    pub(crate) start: Bound<String>,
+    pub(crate) end: Bound<String>,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value out of range (expected ")?;
+        fmt_bounds(f, &self.start, &self.end)?;
+        f.write_str(")")
+    }
+}
+
+impl Error for RangeError {}
+
+/// An error when an environment variable is declared by several fields of a configuration.
+///
+/// The configurations generated by [`make_config!`](crate::make_config) are checked at compile
+/// time, so this error is only reported for the other ones.
+#[derive(Debug, PartialEq)]
+pub struct DuplicateVarError {
+    pub(crate) var_name: &'static str,
+    pub(crate) first_path: Vec<&'static str>,
+    pub(crate) path: Vec<&'static str>,
+}
+
+impl DuplicateVarError {
+    /// Returns the path of the field that first declared the environment variable.
+    #[inline]
+    pub fn first_path(&self) -> &[&'static str] {
+        &self.first_path
+    }
+
+    /// Returns the path of the field that declared the environment variable again.
+    #[inline]
+    pub fn path(&self) -> &[&'static str] {
+        &self.path
+    }
+}
+
+impl fmt::Display for DuplicateVarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the environment variable `{}`", self.var_name)?;
+        if !self.path.is_empty() {
+            write!(f, " of `{}`", self.path.join("."))?;
+        }
+        f.write_str(" is already declared")?;
+        if !self.first_path.is_empty() {
+            write!(f, " by `{}`", self.first_path.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for DuplicateVarError {}
+
 /// A cached error when reading the environment with [`Cached`][1].
 ///
 /// [1]: crate::layers::Cached
@@ -67,7 +378,16 @@ pub struct CachedError<'a, E>(pub(crate) &'a E);
 
 // Note: we can't implement Error for CachedError, because it would conflict
 // with the std impl `From<E: Error + 'a> for Box<dyn Error + 'a>`.
-impl<E> From<CachedError<'_, E>> for Box<dyn Error>
+impl<'b, E> From<CachedError<'_, E>> for Box<dyn Error + 'b>
+where
+    E: fmt::Display,
+{
+    fn from(value: CachedError<'_, E>) -> Self {
+        format!("{}", value.0).into()
+    }
+}
+
+impl<'b, E> From<CachedError<'_, E>> for Box<dyn Error + Send + Sync + 'b>
 where
     E: fmt::Display,
 {
@@ -90,24 +410,337 @@ impl<E: fmt::Debug> fmt::Debug for CachedError<'_, E> {
     }
 }
 
+/// The error returned by the initialization of a configuration, with
+/// [`ConfigInitializer::try_init`][1].
+///
+/// It borrows from the configuration. See [`OwnedConfigInitError`] for a version that doesn't.
+///
+/// [1]: crate::exec::ConfigInitializer::try_init
 pub struct ConfigInitError<'a> {
     pub(crate) error: FmtExecResults<'a>,
 }
 
+impl<'a> ConfigInitError<'a> {
+    /// Returns the report of the read, to inspect the results.
+    #[inline]
+    pub fn report(&self) -> &InitReport<'a> {
+        self.error.report()
+    }
+
+    /// Returns a JSON version of the error report.
+    ///
+    /// See the [`JsonExecResults`] documentation for the schema of the output.
+    #[inline]
+    pub fn json(&self) -> JsonExecResults<'_, 'a> {
+        self.error.json()
+    }
+
+    /// Returns a displayable version of the error, using the provided formatter.
+    ///
+    /// The [`Display`](fmt::Display) implementation uses the
+    /// [global formatter](crate::report::set_formatter) instead.
+    #[inline]
+    pub fn display_with<'r>(&'r self, formatter: &'r dyn ReportFormatter) -> DisplayReport<'r, 'a> {
+        DisplayReport {
+            report: self.report(),
+            formatter,
+            as_error: true,
+        }
+    }
+
+    /// Converts this error into an owned error, that doesn't borrow the configuration anymore.
+    ///
+    /// The error of each variable is converted into its message.
+    #[inline]
+    pub fn into_owned(self) -> OwnedConfigInitError {
+        OwnedConfigInitError {
+            error: self.error.into_owned(),
+        }
+    }
+}
+
 impl fmt::Debug for ConfigInitError<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ConfigInitError")
-            .field("correct_vars", &self.error.correct_vars)
-            .field("incorrect_vars", &self.error.incorrect_vars)
+            .field(
+                "correct_vars",
+                &self.error.correct_vars().collect::<Vec<_>>(),
+            )
+            .field(
+                "incorrect_vars",
+                &self.error.incorrect_vars().collect::<Vec<_>>(),
+            )
             .finish()
     }
 }
 
 impl fmt::Display for ConfigInitError<'_> {
+    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Error during configuration initialization:")?;
-        fmt::Display::fmt(&self.error, f)
+        report::formatter().fmt_init_error(self.report(), f)
     }
 }
 
 impl Error for ConfigInitError<'_> {}
+
+/// The exit code used when the configuration failed to initialize.
+///
+/// This is the conventional `EX_CONFIG` code from `sysexits.h`.
+pub const EX_CONFIG: u8 = 78;
+
+/// An owned version of [`ConfigInitError`].
+///
+/// It doesn't borrow from the configuration, so it can be returned from the `main` function:
+///
+/// ```no_run
+/// # use mkenv::{error::OwnedConfigInitError, prelude::*};
+/// mkenv::make_config! {
+///   struct AppConfig {
+///     user: { var_name: "USER" },
+///   }
+/// }
+///
+/// fn main() -> Result<(), OwnedConfigInitError> {
+///   let config = AppConfig::define();
+///   config.try_init()?;
+///   Ok(())
+/// }
+/// ```
+///
+/// For this purpose, its [`Debug`](fmt::Debug) implementation prints the same report as its
+/// [`Display`](fmt::Display) implementation. Note that with a `Result`, the exit code is the one
+/// of the standard library, i.e. `1`.
+///
+/// It also implements the [`Termination`] trait, by printing the report to the standard error,
+/// and returning the [`EX_CONFIG`] exit code. To use it from the `main` function, see
+/// [`MainResult`].
+pub struct OwnedConfigInitError {
+    error: FmtExecResults<'static>,
+}
+
+impl OwnedConfigInitError {
+    /// Returns the report of the read, to inspect the results.
+    ///
+    /// The error of each variable is its message.
+    #[inline]
+    pub fn report(&self) -> &InitReport<'static> {
+        self.error.report()
+    }
+
+    /// Returns a displayable version of the error, using the provided formatter.
+    ///
+    /// The [`Display`](fmt::Display) implementation uses the
+    /// [global formatter](crate::report::set_formatter) instead.
+    #[inline]
+    pub fn display_with<'r>(
+        &'r self,
+        formatter: &'r dyn ReportFormatter,
+    ) -> DisplayReport<'r, 'static> {
+        DisplayReport {
+            report: self.report(),
+            formatter,
+            as_error: true,
+        }
+    }
+}
+
+impl From<ConfigInitError<'_>> for OwnedConfigInitError {
+    #[inline]
+    fn from(value: ConfigInitError<'_>) -> Self {
+        value.into_owned()
+    }
+}
+
+impl fmt::Debug for OwnedConfigInitError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for OwnedConfigInitError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        report::formatter().fmt_init_error(self.report(), f)
+    }
+}
+
+impl Error for OwnedConfigInitError {}
+
+impl Termination for OwnedConfigInitError {
+    fn report(self) -> ExitCode {
+        eprint!("{self}");
+        ExitCode::from(EX_CONFIG)
+    }
+}
+
+/// The result of a `main` function, exiting with the [`EX_CONFIG`] exit code if the
+/// configuration failed to initialize.
+///
+/// Unlike a `Result`, whose error makes the process exit with the `1` exit code, it uses the
+/// [`Termination`] implementation of [`OwnedConfigInitError`]. It is built from a `Result`:
+///
+/// ```no_run
+/// # use mkenv::{error::{MainResult, OwnedConfigInitError}, prelude::*};
+/// mkenv::make_config! {
+///   struct AppConfig {
+///     user: { var_name: "USER" },
+///   }
+/// }
+///
+/// fn run() -> Result<(), OwnedConfigInitError> {
+///   let config = AppConfig::define();
+///   config.try_init()?;
+///   Ok(())
+/// }
+///
+/// fn main() -> MainResult {
+///   run().into()
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MainResult<T = ()>(pub Result<T, OwnedConfigInitError>);
+
+impl<T, E> From<Result<T, E>> for MainResult<T>
+where
+    E: Into<OwnedConfigInitError>,
+{
+    #[inline]
+    fn from(value: Result<T, E>) -> Self {
+        Self(value.map_err(Into::into))
+    }
+}
+
+impl<T: Termination> Termination for MainResult<T> {
+    #[inline]
+    fn report(self) -> ExitCode {
+        match self.0 {
+            Ok(value) => value.report(),
+            Err(err) => err.report(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        error::Error,
+        process::{ExitCode, Termination},
+        thread,
+    };
+
+    use crate::{
+        error::{
+            ConfigInitError, EX_CONFIG, MainResult, OwnedConfigInitError, ParseError, ReadVarError,
+        },
+        exec::ExecResult,
+        prelude::*,
+        tests::with_env,
+    };
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn assert_errors_send_sync() {
+        assert_send_sync::<ReadVarError>();
+        assert_send_sync::<ParseError>();
+        assert_send_sync::<ExecResult<'_>>();
+        assert_send_sync::<ConfigInitError<'_>>();
+        assert_send_sync::<OwnedConfigInitError>();
+        assert_send_sync::<Box<dyn Error + Send + Sync>>();
+    }
+
+    #[test]
+    fn assert_init_error_from_thread() {
+        crate::make_config! {
+            struct TestConfig {
+                port: {
+                    var_name: "__TEST_INIT_ERROR_FROM_THREAD",
+                    layers: [parsed_from_str<u16>()],
+                },
+            }
+        }
+
+        let config = TestConfig::define();
+
+        let res = with_env([("__TEST_INIT_ERROR_FROM_THREAD", "80a")], || {
+            thread::scope(|s| s.spawn(|| config.try_init()).join().unwrap())
+        });
+        let err = res.unwrap_err();
+        assert!(err.to_string().contains("__TEST_INIT_ERROR_FROM_THREAD"));
+    }
+
+    #[test]
+    fn assert_owned_init_error() {
+        crate::make_config! {
+            struct TestConfig {
+                port: {
+                    var_name: "__TEST_OWNED_INIT_ERROR_PORT",
+                    layers: [parsed_from_str<u16>()],
+                },
+                user: {
+                    var_name: "__TEST_OWNED_INIT_ERROR_USER",
+                },
+            }
+        }
+
+        fn fake_main() -> Result<(), OwnedConfigInitError> {
+            let config = TestConfig::define();
+            config.try_init()?;
+            Ok(())
+        }
+
+        let config = TestConfig::define();
+        let (borrowed_msg, owned_msg, owned_debug) = with_env(
+            [
+                ("__TEST_OWNED_INIT_ERROR_PORT", "80a"),
+                ("__TEST_OWNED_INIT_ERROR_USER", "foo"),
+            ],
+            || {
+                let err = fake_main().unwrap_err();
+                let borrowed_msg = config.try_init().unwrap_err().to_string();
+                (borrowed_msg, err.to_string(), format!("{err:?}"))
+            },
+        );
+
+        assert_eq!(owned_msg, borrowed_msg);
+        assert_eq!(owned_debug, borrowed_msg);
+    }
+
+    #[test]
+    fn assert_main_exit_code() {
+        crate::make_config! {
+            struct TestConfig {
+                port: {
+                    var_name: "__TEST_MAIN_EXIT_CODE_PORT",
+                    layers: [parsed_from_str<u16>()],
+                },
+            }
+        }
+
+        fn run() -> Result<(), OwnedConfigInitError> {
+            TestConfig::define().try_init()?;
+            Ok(())
+        }
+
+        fn result_main() -> Result<(), OwnedConfigInitError> {
+            run()
+        }
+
+        fn wrapped_main() -> MainResult {
+            run().into()
+        }
+
+        // The exit codes are the ones the process would return from the `main` function
+        let (result_code, wrapped_code) = with_env([("__TEST_MAIN_EXIT_CODE_PORT", "80a")], || {
+            (result_main().report(), wrapped_main().report())
+        });
+        assert_eq!(result_code, ExitCode::FAILURE);
+        assert_eq!(wrapped_code, ExitCode::from(EX_CONFIG));
+
+        let ok_code = with_env([("__TEST_MAIN_EXIT_CODE_PORT", "80")], || {
+            wrapped_main().report()
+        });
+        assert_eq!(ok_code, ExitCode::SUCCESS);
+    }
+}
@@ -1,87 +1,435 @@
 //! Contains everything related to the execution of a full read of a configuration.
 
-use std::{error::Error, fmt, panic};
+use std::{error::Error, fmt, panic, process, sync::OnceLock};
 
 use crate::{
-    descriptor::{ConfigValueDescriptor, VarDescriptor},
-    error::ConfigInitError,
-    layer::Layer,
+    descriptor::{ConfigValueDescriptor, DescribeLayer, LayerDescription, VarDescriptor},
+    error::{ConfigInitError, EX_CONFIG, ErrorKind, LayerError},
+    json,
+    layer::{Layer, ReadTrace},
+    report::{self, EntryError, Explanation, InitReport, InitWarnings, ReportFormatter},
 };
 
 /// The result of a read of a configuration value.
 pub struct ExecResult<'a> {
-    #[doc(hidden)]
-    pub config: &'a VarDescriptor,
-    #[doc(hidden)]
-    pub error: Option<Box<dyn Error + 'a>>,
+    pub(crate) config: &'a VarDescriptor,
+    pub(crate) error: Option<Box<dyn Error + Send + Sync + 'a>>,
+    pub(crate) kind: Option<ErrorKind>,
+    pub(crate) trace: ReadTrace,
+    pub(crate) path: Vec<&'static str>,
+    pub(crate) layers: LazyLayers<'a>,
 }
 
 impl<'a> ExecResult<'a> {
-    #[doc(hidden)]
+    /// Creates the result of a read from the descriptor of the configuration value, and the
+    /// outcome of the read.
+    ///
+    /// This is meant for the custom [`ConfigInitializer`] implementations reading values that
+    /// [`from_config`](ExecResult::from_config) doesn't support, e.g. a custom layer whose error
+    /// doesn't implement [`LayerError`]. The [kind](ErrorKind::of) of the error is found by
+    /// inspecting it. The trace and the description of the layers are empty, unless provided
+    /// with [`with_trace`](ExecResult::with_trace) and [`with_layers`](ExecResult::with_layers).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use mkenv::{error::ErrorKind, exec::ExecResult, prelude::*};
+    /// let port = TextVar::from_var_name("PORT");
+    /// let res = ExecResult::new(port.get_descriptor(), Err("not a port".into()));
+    /// assert_eq!(res.descriptor().var_name, "PORT");
+    /// assert_eq!(res.error_kind(), Some(ErrorKind::Other));
+    /// ```
+    pub fn new(
+        descriptor: &'a VarDescriptor,
+        result: Result<(), Box<dyn Error + Send + Sync>>,
+    ) -> Self {
+        let (kind, error) = match result {
+            Ok(()) => (None, None),
+            Err(e) => (Some(ErrorKind::of(&*e)), Some(e as Box<_>)),
+        };
+
+        Self {
+            config: descriptor,
+            error,
+            kind,
+            trace: ReadTrace::new(),
+            path: Vec::new(),
+            layers: LazyLayers::computed(LayerDescription::new()),
+        }
+    }
+
+    /// Sets the trace of the read.
+    #[inline]
+    pub fn with_trace(mut self, trace: ReadTrace) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /// Sets the description of the chain of layers of the configuration value.
+    #[inline]
+    pub fn with_layers(mut self, layers: LayerDescription) -> Self {
+        self.layers = LazyLayers::computed(layers);
+        self
+    }
+
+    /// Returns the descriptor of the configuration value.
+    #[inline]
+    pub fn descriptor(&self) -> &VarDescriptor {
+        self.config
+    }
+
+    /// Returns the error of the read, if it failed.
+    #[inline]
+    pub fn error(&self) -> Option<&(dyn Error + Send + Sync + 'a)> {
+        self.error.as_deref()
+    }
+
+    /// Returns the kind of the error of the read, if it failed.
+    #[inline]
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        self.kind
+    }
+
+    /// Returns the trace of the read.
+    #[inline]
+    pub fn trace(&self) -> &ReadTrace {
+        &self.trace
+    }
+
+    /// Returns the path of the configuration value in the configuration.
+    ///
+    /// It is made of the names of the fields leading to the configuration value, from the root
+    /// configuration. For example, the path of the `db_url` field of a nested configuration
+    /// held by a `db` field is `["db", "db_url"]`.
+    #[inline]
+    pub fn path(&self) -> &[&'static str] {
+        &self.path
+    }
+
+    /// Reads the configuration value, and returns the result of the read.
+    ///
+    /// The configuration value is read with a [trace](ReadTrace), and its layers are
+    /// [described](DescribeLayer::describe). See [`new`](ExecResult::new) for the configuration
+    /// values that don't implement the required traits.
     pub fn from_config<T>(config: &'a T) -> Self
     where
-        &'a T: Layer,
-        Box<dyn Error + 'a>: From<<&'a T as Layer>::Error>,
+        &'a T: Layer<Error: LayerError>,
+        Box<dyn Error + Send + Sync + 'a>: From<<&'a T as Layer>::Error>,
+        T: ConfigValueDescriptor + DescribeLayer,
+    {
+        Self::read(config, LazyLayers::computed(config.describe()))
+    }
+
+    /// Same as [`from_config`](ExecResult::from_config), but only describes the layers if the
+    /// report needs them.
+    ///
+    /// The configuration value is borrowed until then, so it must be [`Sync`] to keep the result
+    /// thread-safe. This is the case of all the layers of the crate, so the
+    /// [`make_config!`](crate::make_config) macro uses it.
+    #[doc(hidden)]
+    pub fn from_sync_config<T>(config: &'a T) -> Self
+    where
+        &'a T: Layer<Error: LayerError>,
+        Box<dyn Error + Send + Sync + 'a>: From<<&'a T as Layer>::Error>,
+        T: ConfigValueDescriptor + DescribeLayer + Sync,
+    {
+        Self::read(config, LazyLayers::new(config))
+    }
+
+    fn read<T>(config: &'a T, layers: LazyLayers<'a>) -> Self
+    where
+        &'a T: Layer<Error: LayerError>,
+        Box<dyn Error + Send + Sync + 'a>: From<<&'a T as Layer>::Error>,
         T: ConfigValueDescriptor,
     {
+        let mut trace = ReadTrace::new();
+        let (kind, error) = match config.try_get_traced(&mut trace) {
+            Ok(_) => (None, None),
+            Err(e) => (Some(e.kind()), Some(From::from(e))),
+        };
+
         Self {
             config: config.get_descriptor(),
-            error: config.try_get().err().map(From::from),
+            error,
+            kind,
+            trace,
+            path: Vec::new(),
+            layers,
         }
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct ExecFailedResult<'a> {
-    config: &'a VarDescriptor,
-    error: Box<dyn Error + 'a>,
+/// The description of the layers of a configuration value, computed when it is first needed.
+pub(crate) struct LazyLayers<'a> {
+    config: Option<&'a (dyn DescribeLayer + Sync)>,
+    layers: OnceLock<LayerDescription>,
+}
+
+impl<'a> LazyLayers<'a> {
+    fn new(config: &'a (dyn DescribeLayer + Sync)) -> Self {
+        Self {
+            config: Some(config),
+            layers: OnceLock::new(),
+        }
+    }
+
+    fn computed(layers: LayerDescription) -> Self {
+        Self {
+            config: None,
+            layers: OnceLock::from(layers),
+        }
+    }
+
+    pub(crate) fn get(&self) -> &LayerDescription {
+        self.layers
+            .get_or_init(|| self.config.map(DescribeLayer::describe).unwrap_or_default())
+    }
+
+    /// Computes the description, to stop borrowing the configuration value.
+    pub(crate) fn into_owned(self) -> LazyLayers<'static> {
+        self.get();
+        LazyLayers {
+            config: None,
+            layers: self.layers,
+        }
+    }
+}
+
+impl fmt::Debug for LazyLayers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.get(), f)
+    }
+}
+
+/// An iterator adapter that prepends a segment to the [path][1] of each result.
+///
+/// It is used by the [`make_config!`](crate::make_config) macro to keep the nesting of the
+/// configuration values.
+///
+/// [1]: ExecResult::path
+#[derive(Debug, Clone)]
+pub struct Prefixed<I> {
+    segment: &'static str,
+    iter: I,
+}
+
+impl<I> Prefixed<I> {
+    /// Wraps the iterator, to prepend the provided segment to the path of each result.
+    #[inline]
+    pub fn new(segment: &'static str, iter: I) -> Self {
+        Self { segment, iter }
+    }
+}
+
+impl<'a, I> Iterator for Prefixed<I>
+where
+    I: Iterator<Item = ExecResult<'a>>,
+{
+    type Item = ExecResult<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut result = self.iter.next()?;
+        result.path.insert(0, self.segment);
+        Some(result)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
 
 /// Formats the results of a whole configuration read.
+///
+/// If a variable isn't set, but some [similar ones](crate::report::ReportEntry::similar_vars)
+/// are, e.g. because of a typo or a different case, they are suggested in the report. Only their
+/// names are shown, never their values.
 pub struct FmtExecResults<'a> {
-    pub(crate) correct_vars: Vec<&'a VarDescriptor>,
-    pub(crate) incorrect_vars: Vec<ExecFailedResult<'a>>,
+    pub(crate) report: InitReport<'a>,
+}
+
+impl<'a> FmtExecResults<'a> {
+    /// Returns the report of the read, to inspect the results.
+    #[inline]
+    pub fn report(&self) -> &InitReport<'a> {
+        &self.report
+    }
+
+    /// Converts this into the report of the read.
+    #[inline]
+    pub fn into_report(self) -> InitReport<'a> {
+        self.report
+    }
+
+    /// Returns a JSON version of the results.
+    ///
+    /// See the [`JsonExecResults`] documentation for the schema of the output.
+    #[inline]
+    pub fn json(&self) -> JsonExecResults<'_, 'a> {
+        JsonExecResults { results: self }
+    }
+
+    /// Returns the descriptors of the variables that were correctly read.
+    pub(crate) fn correct_vars(&self) -> impl Iterator<Item = &VarDescriptor> {
+        self.report.valid().map(|entry| &entry.descriptor)
+    }
+
+    /// Returns the descriptors and errors of the variables that failed to read.
+    pub(crate) fn incorrect_vars(&self) -> impl Iterator<Item = (&VarDescriptor, &EntryError<'a>)> {
+        self.report
+            .iter()
+            .filter_map(|entry| entry.error.as_ref().map(|err| (&entry.descriptor, err)))
+    }
+
+    /// Converts the results into results that don't borrow anything.
+    ///
+    /// The errors are converted into their message.
+    pub(crate) fn into_owned(self) -> FmtExecResults<'static> {
+        FmtExecResults {
+            report: self.report.into_owned(),
+        }
+    }
 }
 
 impl fmt::Display for FmtExecResults<'_> {
+    /// Writes the results with the [global formatter](crate::report::set_formatter).
+    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(
-            f,
-            "Got {} incorrect variable{}",
-            self.incorrect_vars.len(),
-            if self.incorrect_vars.len() > 1 {
-                "s"
-            } else {
-                ""
-            }
-        )?;
+        report::formatter().fmt_report(&self.report, f)
+    }
+}
 
-        for var in &self.incorrect_vars {
-            writeln!(f, "- `{}`: {}", var.config.var_name, var.error)?;
-        }
+/// The JSON version of the results of a whole configuration read.
+///
+/// It is returned by [`FmtExecResults::json`], and its [`Display`](fmt::Display) implementation
+/// writes the JSON document.
+///
+/// ## Schema
+///
+/// The schema is stable: new keys may be added, but the existing ones keep their meaning. The
+/// `version` key is incremented on any breaking change.
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "status": "error",
+///   "valid_count": 1,
+///   "invalid_count": 1,
+///   "unchecked_count": 0,
+///   "variables": [
+///     {
+///       "name": "USER",
+///       "path": "user",
+///       "description": null,
+///       "default": null,
+///       "status": "valid",
+///       "error": null,
+///       "error_kind": null,
+///       "default_used": false,
+///       "similar_vars": [],
+///       "warnings": [
+///         {
+///           "kind": "empty_value",
+///           "message": "the variable is set, but empty"
+///         }
+///       ]
+///     },
+///     {
+///       "name": "REQUEST_TIMEOUT",
+///       "path": "http.request_timeout",
+///       "description": "The request timeout, in milliseconds",
+///       "default": "5000",
+///       "status": "invalid",
+///       "error": "parse error: invalid digit found in string",
+///       "error_kind": "parse",
+///       "default_used": false,
+///       "similar_vars": [],
+///       "warnings": []
+///     }
+///   ]
+/// }
+/// ```
+///
+/// * `status` is `"ok"` if every variable was correctly read, and `"error"` otherwise.
+/// * `unchecked_count` is the number of variables that weren't read, because the read stopped at
+///   the first error (see [`InitMode::FailFast`]).
+/// * `variables` contains the variables in their declaration order.
+/// * For each variable, `path` is its [path](ExecResult::path) in the configuration, joined with
+///   dots. `description` and `default` are the ones of its [descriptor][1], or `null` if not
+///   provided. `default` is also `null` for the secret variables. `status` is either `"valid"` or `"invalid"`. If the variable is invalid, `error`
+///   is the error message, and `error_kind` is one of the [`ErrorKind`] values, in snake case
+///   (e.g. `"not_present"`). Otherwise, they're `null`. `default_used` tells whether the value
+///   was resolved from a default value. `similar_vars` lists the names of the
+///   [similar variables](crate::report::ReportEntry::similar_vars) that are set, if the variable
+///   isn't. `warnings` lists the [warnings](crate::ReadWarning) of the read, with their `kind` in
+///   snake case (e.g. `"empty_value"`) and their `message`.
+///
+/// [1]: crate::VarDescriptor
+pub struct JsonExecResults<'r, 'a> {
+    results: &'r FmtExecResults<'a>,
+}
+
+impl fmt::Display for JsonExecResults<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let report = &self.results.report;
+        let invalid_count = report.invalid_count();
 
-        writeln!(
+        f.write_str("{\"version\":1,\"status\":")?;
+        json::write_str(f, if invalid_count == 0 { "ok" } else { "error" })?;
+        write!(
             f,
-            "Got {} valid variable{}",
-            self.correct_vars.len(),
-            if self.correct_vars.len() > 1 { "s" } else { "" }
+            ",\"valid_count\":{},\"invalid_count\":{invalid_count},\"unchecked_count\":{},\
+             \"variables\":[",
+            report.len() - invalid_count,
+            report.unchecked_count(),
         )?;
 
-        for var in &self.correct_vars {
-            writeln!(f, "- `{}`", var.var_name)?;
-        }
-
-        writeln!(f, "Note: full required environment description:")?;
-        for var_desc in self
-            .incorrect_vars
-            .iter()
-            .map(|v| v.config)
-            .chain(self.correct_vars.iter().copied())
-        {
-            writeln!(f, "- {var_desc}")?;
+        for (i, entry) in report.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str("{\"name\":")?;
+            json::write_str(f, entry.descriptor.var_name)?;
+            f.write_str(",\"path\":")?;
+            json::write_str(f, &entry.path.join("."))?;
+            f.write_str(",\"description\":")?;
+            json::write_opt_str(f, entry.descriptor.description)?;
+            f.write_str(",\"default\":")?;
+            let default_val = entry.descriptor.default_val_fmt();
+            json::write_opt_str(f, default_val.filter(|_| !entry.descriptor.is_secret()))?;
+            f.write_str(",\"status\":")?;
+            json::write_str(f, if entry.is_valid() { "valid" } else { "invalid" })?;
+            f.write_str(",\"error\":")?;
+            match entry.error() {
+                Some(err) => json::write_str(f, &err.to_string())?,
+                None => f.write_str("null")?,
+            }
+            f.write_str(",\"error_kind\":")?;
+            json::write_opt_str(f, entry.error_kind().map(|kind| kind.as_str()))?;
+            write!(f, ",\"default_used\":{}", entry.used_default())?;
+            f.write_str(",\"similar_vars\":[")?;
+            for (i, var_name) in entry.similar_vars().iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                json::write_str(f, var_name)?;
+            }
+            f.write_str("]")?;
+            f.write_str(",\"warnings\":[")?;
+            for (i, warning) in entry.warnings().iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                f.write_str("{\"kind\":")?;
+                json::write_str(f, warning.as_str())?;
+                f.write_str(",\"message\":")?;
+                json::write_str(f, &warning.to_string())?;
+                f.write_str("}")?;
+            }
+            f.write_str("]}")?;
         }
 
-        Ok(())
+        f.write_str("]}")
     }
 }
 
@@ -90,26 +438,96 @@ pub fn fmt_exec_results<'a, I>(results: I) -> FmtExecResults<'a>
 where
     I: IntoIterator<Item = ExecResult<'a>>,
 {
-    let mut incorrect_vars = Vec::new();
-    let mut correct_vars = Vec::new();
-
-    for result in results {
-        if let Some(err) = result.error {
-            incorrect_vars.push(ExecFailedResult {
-                config: result.config,
-                error: err,
-            });
-        } else {
-            correct_vars.push(result.config);
+    FmtExecResults {
+        report: InitReport::new(results),
+    }
+}
+
+/// The way the configuration values are read during an initialization.
+///
+/// See [`ConfigInitializer::try_init_with_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InitMode {
+    /// Reads every configuration value, and reports all the errors.
+    ///
+    /// This is the mode used by [`ConfigInitializer::try_init`].
+    #[default]
+    CollectAll,
+    /// Stops at the first configuration value that failed to read.
+    ///
+    /// The next configuration values aren't read, which avoids slow reads (e.g. of files). The
+    /// report tells how many of them were left unchecked.
+    FailFast,
+}
+
+/// The options of a configuration initialization.
+///
+/// See [`ConfigInitializer::try_init_with_options`].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::{exec::{InitMode, InitOptions}, prelude::*};
+/// let options = InitOptions::new()
+///   .mode(InitMode::FailFast)
+///   .unknown_var_prefixes(&["MYAPP_"]);
+/// assert_eq!(options.get_mode(), InitMode::FailFast);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InitOptions<'p> {
+    mode: InitMode,
+    unknown_var_prefixes: &'p [&'p str],
+}
+
+impl<'p> InitOptions<'p> {
+    /// Returns the default options: every configuration value is read, and no unknown
+    /// environment variable is checked.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            mode: InitMode::CollectAll,
+            unknown_var_prefixes: &[],
         }
     }
 
-    FmtExecResults {
-        correct_vars,
-        incorrect_vars,
+    /// Sets the way the configuration values are read.
+    #[inline]
+    pub const fn mode(mut self, mode: InitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the prefixes of the environment variables checked for misspellings.
+    ///
+    /// When the configuration is successfully read, the environment variables starting with one
+    /// of these prefixes, but that aren't declared by the configuration, are reported as
+    /// [warnings](crate::ReadWarning::UnknownVar) if they're similar to a declared variable,
+    /// which is suggested instead. The prefix itself is ignored when comparing the names.
+    ///
+    /// The other environment variables are ignored. By default, there is no prefix, so nothing is
+    /// checked.
+    #[inline]
+    pub const fn unknown_var_prefixes(mut self, prefixes: &'p [&'p str]) -> Self {
+        self.unknown_var_prefixes = prefixes;
+        self
+    }
+
+    /// Returns the way the configuration values are read.
+    #[inline]
+    pub const fn get_mode(&self) -> InitMode {
+        self.mode
+    }
+
+    /// Returns the prefixes of the environment variables checked for misspellings.
+    #[inline]
+    pub const fn get_unknown_var_prefixes(&self) -> &'p [&'p str] {
+        self.unknown_var_prefixes
     }
 }
 
+/// The iterator returned by [`ConfigInitializer::init_lazy`].
+pub type LazyResults<'a> = Box<dyn Iterator<Item = ExecResult<'a>> + 'a>;
+
 /// Represents types able to read a set of configuration values.
 pub trait ConfigInitializer {
     /// The type of the resulting collection of the read.
@@ -119,6 +537,9 @@ pub trait ConfigInitializer {
 
     /// Reads the whole configuration values set, and returns the result in the form of an iterator.
     ///
+    /// Every configuration value is read by this call. See [`init_lazy`][1] to read them one by
+    /// one instead.
+    ///
     /// # Note
     ///
     /// Please note that for types using a cached configuration, this method will make it use
@@ -150,8 +571,51 @@ pub trait ConfigInitializer {
     /// // will always be the same.
     /// let user = config.user.get();
     /// ```
+    ///
+    /// [1]: ConfigInitializer::init_lazy
     fn init_raw(&self) -> Self::Iter<'_>;
 
+    /// Returns an iterator reading each configuration value when it reaches it.
+    ///
+    /// Unlike [`init_raw`][1], nothing is read by this call, so the configuration values that
+    /// aren't reached, e.g. because the iteration stopped at the first error, aren't read at all.
+    /// This is what [`InitMode::FailFast`] uses.
+    ///
+    /// The default implementation isn't lazy, it iterates over the result of
+    /// [`init_raw`][1]. The implementation generated by [`make_config!`](crate::make_config)
+    /// is.
+    ///
+    /// [1]: ConfigInitializer::init_raw
+    fn init_lazy(&self) -> LazyResults<'_> {
+        Box::new(self.init_raw().into_iter())
+    }
+
+    /// Reads the configuration, and returns the report of the read.
+    ///
+    /// If some fields use a cached layer, consider reading the note about [caching][1]
+    ///
+    /// Unlike [`try_init`][2], it always returns the report, that can be inspected to write
+    /// a custom one.
+    ///
+    /// [1]: ConfigInitializer#note-about-caching
+    /// [2]: ConfigInitializer::try_init
+    fn init_report(&self) -> InitReport<'_> {
+        InitReport::new(self.init_raw())
+    }
+
+    /// Reads the configuration, and returns the explanation of how each variable was resolved.
+    ///
+    /// If some fields use a cached layer, consider reading the note about [caching][1]
+    ///
+    /// See [`Explanation`] for more information.
+    ///
+    /// [1]: ConfigInitializer#note-about-caching
+    fn explain(&self) -> Explanation<'_> {
+        Explanation {
+            report: self.init_report(),
+        }
+    }
+
     /// Reads the configuration, and returns a formatted result in case of any error.
     ///
     /// If some fields use a cached layer, consider reading the note about [caching][1]
@@ -159,15 +623,105 @@ pub trait ConfigInitializer {
     /// # Returns
     ///
     /// This method returns `Err(_)` if any configuration value failed to read, and `Ok(_)`
-    /// otherwise.
+    /// otherwise, with the [warnings](crate::ReadWarning) of the read. To also check the unknown
+    /// environment variables, see [`try_init_with_options`][2].
+    ///
+    /// [1]: ConfigInitializer#note-about-caching
+    /// [2]: ConfigInitializer::try_init_with_options
+    fn try_init(&self) -> Result<InitWarnings, ConfigInitError<'_>> {
+        self.try_init_with_mode(InitMode::CollectAll)
+    }
+
+    /// Reads the configuration with the provided mode, and returns a formatted result in case
+    /// of any error.
+    ///
+    /// If some fields use a cached layer, consider reading the note about [caching][1]
+    ///
+    /// # Returns
+    ///
+    /// This method returns `Err(_)` if any configuration value failed to read, and `Ok(_)`
+    /// otherwise, with the [warnings](crate::ReadWarning) of the read. With
+    /// [`InitMode::FailFast`], the error only contains the first failure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mkenv::{exec::InitMode, prelude::*};
+    /// mkenv::make_config! {
+    ///   struct AppConfig {
+    ///     db_url: { var_name: "FAIL_FAST_DB_URL" },
+    ///     certs: {
+    ///       var_name: "FAIL_FAST_CERTS_PATH",
+    ///       layers: [file_read()],
+    ///     },
+    ///   }
+    /// }
+    ///
+    /// let config = AppConfig::define();
+    /// let err = config.try_init_with_mode(InitMode::FailFast).unwrap_err();
+    /// assert_eq!(err.report().invalid_count(), 1);
+    /// assert_eq!(err.report().unchecked_count(), 1);
+    /// ```
+    ///
+    /// [1]: ConfigInitializer#note-about-caching
+    fn try_init_with_mode(&self, mode: InitMode) -> Result<InitWarnings, ConfigInitError<'_>> {
+        self.try_init_with_options(InitOptions::new().mode(mode))
+    }
+
+    /// Reads the configuration with the provided options, and returns a formatted result in case
+    /// of any error.
+    ///
+    /// If some fields use a cached layer, consider reading the note about [caching][1]
+    ///
+    /// # Returns
+    ///
+    /// This method returns `Err(_)` if any configuration value failed to read, and `Ok(_)`
+    /// otherwise, with the [warnings](crate::ReadWarning) of the read, including the
+    /// [unknown environment variables](InitOptions::unknown_var_prefixes).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mkenv::{exec::InitOptions, prelude::*, ReadWarning};
+    /// # unsafe { std::env::set_var("MYAPP_DB_URL", "postgres://"); }
+    /// # unsafe { std::env::set_var("MYAPP_DB_ULR", "postgres://"); }
+    /// mkenv::make_config! {
+    ///   struct AppConfig {
+    ///     db_url: { var_name: "MYAPP_DB_URL" },
+    ///   }
+    /// }
+    ///
+    /// let config = AppConfig::define();
+    /// let options = InitOptions::new().unknown_var_prefixes(&["MYAPP_"]);
+    /// let warnings = config.try_init_with_options(options).unwrap();
+    /// let warning = warnings.iter().next().unwrap();
+    /// assert_eq!(warning.descriptor().var_name, "MYAPP_DB_URL");
+    /// assert_eq!(
+    ///   *warning.warning(),
+    ///   ReadWarning::UnknownVar { var_name: "MYAPP_DB_ULR".to_owned() },
+    /// );
+    /// ```
     ///
     /// [1]: ConfigInitializer#note-about-caching
-    fn try_init(&self) -> Result<(), ConfigInitError<'_>> {
-        let res = fmt_exec_results(self.init_raw());
-        if res.incorrect_vars.is_empty() {
-            Ok(())
+    fn try_init_with_options(
+        &self,
+        options: InitOptions<'_>,
+    ) -> Result<InitWarnings, ConfigInitError<'_>> {
+        let mode = options.mode;
+        let report = match mode {
+            InitMode::CollectAll => InitReport::with_mode(self.init_raw(), mode),
+            InitMode::FailFast => InitReport::with_mode(self.init_lazy(), mode),
+        };
+        if report.is_ok() {
+            let mut warnings = report.to_warnings();
+            warnings
+                .warnings
+                .extend(report.unknown_var_warnings(options.unknown_var_prefixes));
+            Ok(warnings)
         } else {
-            Err(ConfigInitError { error: res })
+            Err(ConfigInitError {
+                error: FmtExecResults { report },
+            })
         }
     }
 
@@ -175,14 +729,333 @@ pub trait ConfigInitializer {
     ///
     /// If some fields use a cached layer, consider reading the note about [caching][1]
     ///
+    /// If the read succeeds with [warnings](crate::ReadWarning), they're printed to the standard
+    /// error.
+    ///
     /// # Panics
     ///
     /// This method panics if any configuration value failed to read.
     ///
     /// [1]: ConfigInitializer#note-about-caching
     fn init(&self) {
-        self.try_init().unwrap_or_else(|e| {
+        let warnings = self.try_init().unwrap_or_else(|e| {
             panic!("{e}");
         });
+        if !warnings.is_empty() {
+            eprint!("{warnings}");
+        }
+    }
+
+    /// Reads the configuration with the provided mode, and panics in case of any error.
+    ///
+    /// If some fields use a cached layer, consider reading the note about [caching][1]
+    ///
+    /// # Panics
+    ///
+    /// This method panics if any configuration value failed to read.
+    ///
+    /// [1]: ConfigInitializer#note-about-caching
+    fn init_with_mode(&self, mode: InitMode) {
+        let warnings = self.try_init_with_mode(mode).unwrap_or_else(|e| {
+            panic!("{e}");
+        });
+        if !warnings.is_empty() {
+            eprint!("{warnings}");
+        }
+    }
+
+    /// Reads the configuration, and panics in case of any error, formatting the panic message
+    /// with the provided formatter.
+    ///
+    /// If some fields use a cached layer, consider reading the note about [caching][1]
+    ///
+    /// # Panics
+    ///
+    /// This method panics if any configuration value failed to read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use mkenv::{prelude::*, report::Compact};
+    /// mkenv::make_config! {
+    ///   struct AppConfig {
+    ///     user: { var_name: "USER" },
+    ///   }
+    /// }
+    ///
+    /// let config = AppConfig::define();
+    /// config.init_with(&Compact);
+    /// ```
+    ///
+    /// [1]: ConfigInitializer#note-about-caching
+    fn init_with(&self, formatter: &dyn ReportFormatter) {
+        let warnings = self.try_init().unwrap_or_else(|e| {
+            panic!("{}", e.display_with(formatter));
+        });
+        if !warnings.is_empty() {
+            eprint!("{}", warnings.display_with(formatter));
+        }
+    }
+
+    /// Reads the configuration, and exits the process in case of any error.
+    ///
+    /// If some fields use a cached layer, consider reading the note about [caching][1]
+    ///
+    /// Unlike [`init`][2], it doesn't panic, but prints the error report to the standard error,
+    /// and exits the process with the [`EX_CONFIG`] exit code.
+    ///
+    /// [1]: ConfigInitializer#note-about-caching
+    /// [2]: ConfigInitializer::init
+    fn init_or_exit(&self) {
+        match self.try_init() {
+            Ok(warnings) if !warnings.is_empty() => eprint!("{warnings}"),
+            Ok(_) => {}
+            Err(e) => {
+                eprint!("{e}");
+                process::exit(EX_CONFIG.into());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use crate::{
+        ConfigValueDescriptor, DescribeLayer, ReadTrace, VarDescriptor,
+        error::ErrorKind,
+        exec::{ConfigInitializer, ExecResult, InitMode, fmt_exec_results},
+        prelude::*,
+        tests::with_env,
+    };
+
+    crate::make_config! {
+        struct TestConfig {
+            user: {
+                var_name: "__TEST_EXEC_USER",
+            },
+            timeout: {
+                var_name: "__TEST_EXEC_TIMEOUT",
+                layers: [parsed_from_str<u64>()],
+                description: "The \"request\" timeout",
+                default_val_fmt: "5000",
+            },
+            missing: {
+                var_name: "__TEST_EXEC_MISSING",
+            },
+        }
+    }
+
+    #[test]
+    fn assert_error_kinds() {
+        let config = TestConfig::define();
+        let res = with_env(
+            [("__TEST_EXEC_USER", "foo"), ("__TEST_EXEC_TIMEOUT", "5s")],
+            || fmt_exec_results(config.init_raw()),
+        );
+
+        itertools::assert_equal(
+            res.report().iter().map(|entry| entry.error_kind()),
+            [None, Some(ErrorKind::Parse), Some(ErrorKind::NotPresent)],
+        );
+    }
+
+    #[test]
+    fn assert_custom_results() {
+        struct CustomConfig {
+            port: TextVar,
+        }
+
+        impl ConfigInitializer for CustomConfig {
+            type Iter<'a> = Vec<ExecResult<'a>>;
+
+            fn init_raw(&self) -> Self::Iter<'_> {
+                let mut trace = ReadTrace::new();
+                let res = self
+                    .port
+                    .try_get_traced(&mut trace)
+                    .map(drop)
+                    .map_err(|e| io::Error::other(e).into());
+                vec![
+                    ExecResult::new(self.port.get_descriptor(), res)
+                        .with_trace(trace)
+                        .with_layers(self.port.describe()),
+                ]
+            }
+        }
+
+        let config = CustomConfig {
+            port: TextVar::from_var_name("__TEST_EXEC_CUSTOM_PORT"),
+        };
+        let report = with_env([], || config.init_report());
+        let entry = report.iter().next().unwrap();
+        assert_eq!(entry.descriptor().var_name, "__TEST_EXEC_CUSTOM_PORT");
+        assert_eq!(entry.error_kind(), Some(ErrorKind::Io));
+        assert_eq!(entry.trace().var_present(), Some(false));
+        assert_eq!(entry.layers().to_string(), "text");
+    }
+
+    #[test]
+    fn assert_json_report() {
+        let config = TestConfig::define();
+        let res = with_env(
+            [
+                ("__TEST_EXEC_USER", "foo"),
+                ("__TEST_EXEC_TIMEOUT", "5s"),
+                ("__test_exec_missing", "bar"),
+            ],
+            || fmt_exec_results(config.init_raw()),
+        );
+
+        assert_eq!(
+            res.json().to_string(),
+            concat!(
+                r#"{"version":1,"status":"error","valid_count":1,"invalid_count":2,"#,
+                r#""unchecked_count":0,"variables":["#,
+                r#"{"name":"__TEST_EXEC_USER","path":"user","description":null,"default":null,"#,
+                r#""status":"valid","error":null,"error_kind":null,"default_used":false,"#,
+                r#""similar_vars":[],"warnings":[]},"#,
+                r#"{"name":"__TEST_EXEC_TIMEOUT","path":"timeout","description":"The \"request\" timeout","#,
+                r#""default":"5000","status":"invalid","#,
+                r#""error":"parse error: invalid digit found in string","error_kind":"parse","#,
+                r#""default_used":false,"similar_vars":[],"warnings":[]},"#,
+                r#"{"name":"__TEST_EXEC_MISSING","path":"missing","description":null,"default":null,"#,
+                r#""status":"invalid","error":"environment variable not found","#,
+                r#""error_kind":"not_present","default_used":false,"#,
+                r#""similar_vars":["__test_exec_missing"],"warnings":[]}]}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn assert_json_report_ok() {
+        let config = TestConfig::define();
+        let res = with_env(
+            [
+                ("__TEST_EXEC_USER", "foo"),
+                ("__TEST_EXEC_TIMEOUT", "5"),
+                ("__TEST_EXEC_MISSING", "bar"),
+            ],
+            || fmt_exec_results(config.init_raw()),
+        );
+
+        assert!(
+            res.json()
+                .to_string()
+                .starts_with(r#"{"version":1,"status":"ok","valid_count":3,"invalid_count":0,"#)
+        );
+    }
+
+    #[test]
+    fn assert_fail_fast() {
+        let config = TestConfig::define();
+
+        let (err, msg) = with_env([("__TEST_EXEC_TIMEOUT", "5s")], || {
+            let err = config.try_init_with_mode(InitMode::FailFast).unwrap_err();
+            let msg = err.to_string();
+            (err, msg)
+        });
+        let report = err.report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report.invalid_count(), 1);
+        assert_eq!(report.unchecked_count(), 2);
+        assert!(msg.contains("Stopped at the first error, 2 variables left unchecked"));
+
+        let err = with_env([("__TEST_EXEC_TIMEOUT", "5s")], || {
+            config.try_init().unwrap_err()
+        });
+        assert_eq!(err.report().invalid_count(), 3);
+        assert_eq!(err.report().unchecked_count(), 0);
+    }
+
+    #[test]
+    fn assert_fail_fast_ok() {
+        let config = TestConfig::define();
+        let res = with_env(
+            [
+                ("__TEST_EXEC_USER", "foo"),
+                ("__TEST_EXEC_TIMEOUT", "5"),
+                ("__TEST_EXEC_MISSING", "bar"),
+            ],
+            || config.try_init_with_mode(InitMode::FailFast).is_ok(),
+        );
+        assert!(res);
+    }
+
+    #[test]
+    fn assert_init_raw_eager() {
+        crate::make_config! {
+            struct CachedConfig {
+                first: {
+                    var_name: "__TEST_EXEC_EAGER_FIRST",
+                },
+                second: {
+                    var_name: "__TEST_EXEC_EAGER_SECOND",
+                    layers: [cached()],
+                },
+            }
+        }
+
+        // The iterator isn't consumed, but every value is read
+        let config = CachedConfig::define();
+        with_env([("__TEST_EXEC_EAGER_SECOND", "foo")], || {
+            let _ = config.init_raw();
+        });
+        assert_eq!(config.second.get(), "foo");
+
+        // The values after the first error aren't read
+        let config = CachedConfig::define();
+        with_env([("__TEST_EXEC_EAGER_SECOND", "foo")], || {
+            let _ = config.try_init_with_mode(InitMode::FailFast);
+        });
+        with_env([("__TEST_EXEC_EAGER_SECOND", "bar")], || {
+            assert_eq!(config.second.get(), "bar");
+        });
+    }
+
+    #[test]
+    fn assert_lazy_layers() {
+        crate::make_config! {
+            struct LazyConfig {
+                port: {
+                    var_name: "__TEST_EXEC_LAZY_PORT",
+                    layers: [parsed_from_str<u16>()],
+                },
+                user: {
+                    var_name: "__TEST_EXEC_LAZY_USER",
+                    layers: [cached()],
+                },
+            }
+        }
+
+        let config = LazyConfig::define();
+        let report = with_env([], || config.init_report());
+        // Nothing is described during the read
+        assert!(report.iter().all(|entry| entry.layers.config.is_some()));
+        itertools::assert_equal(
+            report.iter().map(|entry| entry.layers().to_string()),
+            ["text → parse u16", "text → cached"],
+        );
+    }
+
+    #[test]
+    fn assert_default_describe_layer() {
+        struct Custom(TextVar);
+
+        impl ConfigValueDescriptor for Custom {
+            fn get_descriptor(&self) -> &VarDescriptor {
+                self.0.get_descriptor()
+            }
+        }
+
+        impl DescribeLayer for Custom {}
+
+        assert_eq!(
+            Custom(TextVar::from_var_name("__TEST_EXEC_CUSTOM"))
+                .describe()
+                .to_string(),
+            "Custom"
+        );
     }
 }
@@ -0,0 +1,28 @@
+//! Internal helpers to write JSON documents, as the crate has no dependency.
+
+use std::fmt;
+
+/// Writes the provided string as a JSON string, with its surrounding quotes.
+pub(crate) fn write_str(f: &mut impl fmt::Write, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Writes the provided optional string as a JSON string, or `null` if absent.
+pub(crate) fn write_opt_str(f: &mut impl fmt::Write, s: Option<&str>) -> fmt::Result {
+    match s {
+        Some(s) => write_str(f, s),
+        None => f.write_str("null"),
+    }
+}
@@ -1,6 +1,6 @@
-use std::fmt;
+use std::{env::VarError, fmt, path::PathBuf};
 
-use crate::descriptor::ConfigValueDescriptor;
+use crate::{descriptor::ConfigValueDescriptor, error::ErrorKind, report::format::DisplayVarError};
 
 /// Represents types able to read a value from the process environment.
 #[cfg_attr(feature = "nightly", doc(notable_trait))]
@@ -13,23 +13,36 @@ pub trait Layer {
     /// Reads and returns the value from the environment, or returns an error.
     fn try_get(&self) -> Result<Self::Output, Self::Error>;
 
+    /// Same as [`try_get`](Layer::try_get), but also records how the value was resolved into the
+    /// provided trace.
+    ///
+    /// The default implementation simply calls `try_get`, and records nothing. Layers wrapping
+    /// another one should forward the trace to it.
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        let _ = trace;
+        self.try_get()
+    }
+
     /// Reads and returns the value from the environment, or panics on error.
     ///
     /// # Panics
     ///
     /// This function panics if it couldn't read the value from the environment, by printing
-    /// a formatted message about the variable that failed.
+    /// a formatted message about the variable that failed. The message is written by the
+    /// [global formatter](crate::report::set_formatter).
     fn get(&self) -> Self::Output
     where
         Self: ConfigValueDescriptor,
         Self::Error: fmt::Display,
     {
         self.try_get().unwrap_or_else(|e| {
-            let val_config = <Self as ConfigValueDescriptor>::get_descriptor(self);
             panic!(
-                "couldn't get env var `{}` (expected type `{}`): {e}",
-                val_config.var_name,
-                std::any::type_name::<Self::Output>(),
+                "{}",
+                DisplayVarError {
+                    descriptor: <Self as ConfigValueDescriptor>::get_descriptor(self),
+                    type_name: std::any::type_name::<Self::Output>(),
+                    error: &e,
+                }
             );
         })
     }
@@ -42,4 +55,272 @@ impl<T: Layer> Layer for &T {
     fn try_get(&self) -> Result<Self::Output, Self::Error> {
         <T as Layer>::try_get(self)
     }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        <T as Layer>::try_get_traced(self, trace)
+    }
+}
+
+/// Represents layers that can only fail because of the environment variable itself.
+///
+/// Such layers can be wrapped by the layers keeping the concrete type of their own errors, such
+/// as [`TypedParsed`](crate::layers::TypedParsed).
+pub trait VarLayer: Layer {
+    /// Same as [`try_get_traced`](Layer::try_get_traced), but returns the error related to the
+    /// environment variable.
+    fn try_get_var_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, VarError>;
+}
+
+impl<T: VarLayer> VarLayer for &T {
+    fn try_get_var_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, VarError> {
+        <T as VarLayer>::try_get_var_traced(self, trace)
+    }
+}
+
+/// Records how a configuration value was resolved during a read.
+///
+/// It is filled by [`Layer::try_get_traced`], and is available for each variable in the
+/// [report](crate::report::InitReport) of a configuration read. It contains the
+/// [steps](ReadStep) of the resolution, in their order, and the [warnings](ReadWarning) of the
+/// read.
+#[derive(Debug, Clone, Default)]
+pub struct ReadTrace {
+    steps: Vec<ReadStep>,
+    warnings: Vec<ReadWarning>,
+}
+
+impl ReadTrace {
+    /// Returns an empty trace.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a step of the resolution.
+    #[inline]
+    pub fn record_step(&mut self, step: ReadStep) {
+        self.steps.push(step);
+    }
+
+    /// Records the read of an environment variable.
+    ///
+    /// This is meant to be called by the layers reading the environment, such as
+    /// [`TextVar`](crate::layers::TextVar).
+    #[inline]
+    pub fn record_var(&mut self, var_name: &'static str, present: bool) {
+        self.record_step(ReadStep::Var { var_name, present });
+    }
+
+    /// Records that the value was resolved from a default value.
+    ///
+    /// This is meant to be called by layers providing a fallback value, such as
+    /// [`OrDefault`](crate::layers::OrDefault).
+    #[inline]
+    pub fn record_default(&mut self, cause: DefaultCause) {
+        self.record_step(ReadStep::Default { cause });
+    }
+
+    /// Returns the steps of the resolution, in their order.
+    #[inline]
+    pub fn steps(&self) -> &[ReadStep] {
+        &self.steps
+    }
+
+    /// Returns the step the value was resolved from, i.e. the last step.
+    ///
+    /// If the read failed, it is the last step before the failure.
+    #[inline]
+    pub fn source(&self) -> Option<&ReadStep> {
+        self.steps.last()
+    }
+
+    /// Returns whether the value was resolved from a default value.
+    #[inline]
+    pub fn used_default(&self) -> bool {
+        self.default_cause().is_some()
+    }
+
+    /// Returns the reason why a default value was used, if any.
+    pub fn default_cause(&self) -> Option<DefaultCause> {
+        self.steps.iter().find_map(|step| match step {
+            ReadStep::Default { cause } => Some(*cause),
+            _ => None,
+        })
+    }
+
+    /// Returns whether the environment variable was set, or `None` if it wasn't recorded.
+    ///
+    /// If several environment variables were read, e.g. with a deprecated alias, it is whether
+    /// the last one was set.
+    pub fn var_present(&self) -> Option<bool> {
+        self.steps.iter().rev().find_map(|step| match step {
+            ReadStep::Var { present, .. } => Some(*present),
+            _ => None,
+        })
+    }
+
+    /// Records a warning about the read.
+    ///
+    /// A warning doesn't make the read fail. It is shown in the report of the configuration
+    /// read.
+    #[inline]
+    pub fn record_warning(&mut self, warning: ReadWarning) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Returns the warnings recorded during the read.
+    #[inline]
+    pub fn warnings(&self) -> &[ReadWarning] {
+        &self.warnings
+    }
+
+    /// Removes a previously recorded warning.
+    pub(crate) fn remove_warning(&mut self, warning: &ReadWarning) {
+        self.warnings.retain(|w| w != warning);
+    }
+
+    /// Adds the records of the other trace to this one.
+    pub(crate) fn extend(&mut self, other: &ReadTrace) {
+        self.steps.extend(other.steps.iter().cloned());
+        for warning in &other.warnings {
+            self.record_warning(warning.clone());
+        }
+    }
+}
+
+/// A step of the resolution of a configuration value, recorded in a [`ReadTrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReadStep {
+    /// An environment variable was read.
+    Var {
+        /// The name of the environment variable.
+        var_name: &'static str,
+        /// Whether the environment variable was set.
+        present: bool,
+    },
+    /// A file was read.
+    File {
+        /// The path to the file.
+        path: PathBuf,
+    },
+    /// A default value was used.
+    Default {
+        /// The reason why the default value was used.
+        cause: DefaultCause,
+    },
+}
+
+impl fmt::Display for ReadStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Var {
+                var_name,
+                present: true,
+            } => write!(f, "read the environment variable `{var_name}`"),
+            Self::Var {
+                var_name,
+                present: false,
+            } => write!(f, "the environment variable `{var_name}` isn't set"),
+            Self::File { path } => write!(f, "read the file `{}`", path.display()),
+            Self::Default { cause } => write!(f, "used the default value, because {cause}"),
+        }
+    }
+}
+
+/// The reason why a default value was used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DefaultCause {
+    /// The environment variable isn't set.
+    NotPresent,
+    /// The read of the value failed, e.g. because it is invalid.
+    ReadFailed {
+        /// The kind of the error of the read.
+        kind: ErrorKind,
+    },
+}
+
+impl fmt::Display for DefaultCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotPresent
+            | Self::ReadFailed {
+                kind: ErrorKind::NotPresent,
+            } => f.write_str("the environment variable isn't set"),
+            Self::ReadFailed { kind } => match kind {
+                ErrorKind::NotUnicode => f.write_str("the value isn't valid unicode"),
+                ErrorKind::Parse => f.write_str("the value couldn't be parsed"),
+                ErrorKind::Io => f.write_str("an I/O error occurred"),
+                ErrorKind::Invalid => f.write_str("the value isn't allowed"),
+                _ => f.write_str("the read failed"),
+            },
+        }
+    }
+}
+
+/// A non-fatal issue about the read of a configuration value.
+///
+/// Warnings are recorded in the [`ReadTrace`] of the read. They don't make the initialization of
+/// the configuration fail, but are shown with its report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReadWarning {
+    /// The environment variable is set, but its value is invalid, so the default value was used.
+    DefaultOnInvalid,
+    /// The value was read from a deprecated name of the environment variable.
+    DeprecatedAlias {
+        /// The deprecated name.
+        alias: &'static str,
+    },
+    /// The environment variable is set, but empty.
+    EmptyValue,
+    /// The value is the same as the default value, so it could be removed from the environment.
+    SameAsDefault,
+    /// An environment variable that isn't declared by the configuration is set, with one of the
+    /// [checked prefixes](crate::exec::InitOptions::unknown_var_prefixes). It may be a misspelling
+    /// of the variable this warning relates to, which is the closest similar declared one.
+    ///
+    /// Unlike the other warnings, it isn't recorded during the read of a variable, but by
+    /// [`ConfigInitializer::try_init_with_options`][1].
+    ///
+    /// [1]: crate::exec::ConfigInitializer::try_init_with_options
+    UnknownVar {
+        /// The name of the unknown variable.
+        var_name: String,
+    },
+}
+
+impl ReadWarning {
+    /// Returns the name of the warning, in snake case.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DefaultOnInvalid => "default_on_invalid",
+            Self::DeprecatedAlias { .. } => "deprecated_alias",
+            Self::EmptyValue => "empty_value",
+            Self::SameAsDefault => "same_as_default",
+            Self::UnknownVar { .. } => "unknown_var",
+        }
+    }
+}
+
+impl fmt::Display for ReadWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DefaultOnInvalid => f.write_str("invalid value, the default value was used"),
+            Self::DeprecatedAlias { alias } => {
+                write!(f, "read from the deprecated variable `{alias}`")
+            }
+            Self::EmptyValue => f.write_str("the variable is set, but empty"),
+            Self::SameAsDefault => f.write_str("the value is the same as the default value"),
+            Self::UnknownVar { var_name } => {
+                write!(
+                    f,
+                    "the unknown variable `{var_name}` is set, did you mean this one?"
+                )
+            }
+        }
+    }
 }
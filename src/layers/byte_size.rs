@@ -0,0 +1,244 @@
+//! Module containing everything related to the [`ByteSize`] configuration value type.
+
+use crate::{
+    descriptor::{
+        ConfigValueDescriptor, Constraint, DescribeLayer, LayerDescription, VarDescriptor,
+    },
+    error::{ByteSizeError, ReadVarError},
+    layer::{Layer, ReadTrace},
+};
+
+/// The supported unit suffixes, with their multiplier.
+///
+/// The suffixes are compared case-insensitively.
+const UNITS: &[(&str, u64)] = &[
+    ("", 1),
+    ("b", 1),
+    ("k", 1000),
+    ("kb", 1000),
+    ("m", 1000u64.pow(2)),
+    ("mb", 1000u64.pow(2)),
+    ("g", 1000u64.pow(3)),
+    ("gb", 1000u64.pow(3)),
+    ("t", 1000u64.pow(4)),
+    ("tb", 1000u64.pow(4)),
+    ("p", 1000u64.pow(5)),
+    ("pb", 1000u64.pow(5)),
+    ("e", 1000u64.pow(6)),
+    ("eb", 1000u64.pow(6)),
+    ("ki", 1 << 10),
+    ("kib", 1 << 10),
+    ("mi", 1 << 20),
+    ("mib", 1 << 20),
+    ("gi", 1 << 30),
+    ("gib", 1 << 30),
+    ("ti", 1 << 40),
+    ("tib", 1 << 40),
+    ("pi", 1 << 50),
+    ("pib", 1 << 50),
+    ("ei", 1 << 60),
+    ("eib", 1 << 60),
+];
+
+/// The maximum amount of fractional digits taken into account.
+///
+/// Further digits are ignored, as they can't change the result by a whole byte.
+const MAX_FRACTION_DIGITS: usize = 19;
+
+/// A configuration value that parses a size in bytes from the content of the inner configuration
+/// value.
+///
+/// The accepted format is a number, optionally followed by a unit suffix, e.g. `512MiB`, `10 MB`
+/// or `1.5GiB`. Both SI (`kB`, `MB`, `GB`, ..., powers of 1000) and IEC (`KiB`, `MiB`, `GiB`, ...,
+/// powers of 1024) suffixes are supported, case-insensitively. The trailing `B` may be omitted,
+/// and a number without suffix is a count of bytes.
+///
+/// Decimal fractions are supported. If the resulting size isn't a whole number of bytes, it is
+/// rounded down.
+///
+/// To construct it, see [`byte_size`][1].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::prelude::*;
+/// # unsafe { std::env::set_var("CACHE_SIZE", "1.5KiB"); }
+/// let my_config = TextVar::from_var_name("CACHE_SIZE").byte_size();
+/// let res = my_config.try_get();
+/// # unsafe { std::env::remove_var("CACHE_SIZE"); }
+/// assert_eq!(res, Ok(1536));
+/// ```
+///
+/// [1]: crate::builder::LayerExt::byte_size
+pub struct ByteSize<V> {
+    pub(crate) var: V,
+}
+
+impl<V: ConfigValueDescriptor> ConfigValueDescriptor for ByteSize<V> {
+    #[inline]
+    fn get_descriptor(&self) -> &VarDescriptor {
+        self.var.get_descriptor()
+    }
+}
+
+impl<V: DescribeLayer> DescribeLayer for ByteSize<V> {
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        desc.push::<u64>("byte size");
+        desc.push_constraint(Constraint::ByteSize);
+    }
+}
+
+impl<V> Layer for ByteSize<V>
+where
+    V: Layer<Output: AsRef<str>>,
+    ReadVarError: From<<V as Layer>::Error>,
+{
+    type Output = u64;
+    type Error = ReadVarError;
+
+    fn try_get(&self) -> Result<Self::Output, Self::Error> {
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        let raw_val = self.var.try_get_traced(trace)?;
+        parse_byte_size(raw_val.as_ref()).map_err(ReadVarError::other)
+    }
+}
+
+/// Parses the provided input into a count of bytes.
+pub(crate) fn parse_byte_size(input: &str) -> Result<u64, ByteSizeError> {
+    let input = input.trim();
+    let num_len = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (num, unit) = input.split_at(num_len);
+    let unit = unit.trim_start();
+
+    let (int_part, frac_part) = num.split_once('.').unwrap_or((num, ""));
+    if int_part.is_empty() && frac_part.is_empty() || frac_part.contains('.') {
+        return Err(ByteSizeError::InvalidNumber);
+    }
+
+    let multiplier = UNITS
+        .iter()
+        .find(|(suffix, _)| suffix.eq_ignore_ascii_case(unit))
+        .map(|(_, multiplier)| *multiplier as u128)
+        .ok_or(ByteSizeError::UnknownUnit)?;
+
+    let int_val = int_part
+        .bytes()
+        .try_fold(0u128, |acc, b| {
+            acc.checked_mul(10)?.checked_add((b - b'0') as u128)
+        })
+        .and_then(|v| v.checked_mul(multiplier))
+        .ok_or(ByteSizeError::Overflow)?;
+
+    let frac_part = &frac_part[..frac_part.len().min(MAX_FRACTION_DIGITS)];
+    let frac_val = frac_part
+        .bytes()
+        .fold(0u128, |acc, b| acc * 10 + (b - b'0') as u128)
+        * multiplier
+        / 10u128.pow(frac_part.len() as u32);
+
+    int_val
+        .checked_add(frac_val)
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or(ByteSizeError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::{
+        error::{ByteSizeError, ReadVarError},
+        prelude::*,
+        tests::{assert_matches, with_env},
+    };
+
+    use super::parse_byte_size;
+
+    #[test]
+    fn assert_units() {
+        assert_matches!(parse_byte_size("42"), Ok(42));
+        assert_matches!(parse_byte_size("42B"), Ok(42));
+        assert_matches!(parse_byte_size("10MB"), Ok(10_000_000));
+        assert_matches!(parse_byte_size("10 mb"), Ok(10_000_000));
+        assert_matches!(parse_byte_size("512MiB"), Ok(536_870_912));
+        assert_matches!(parse_byte_size("2k"), Ok(2000));
+        assert_matches!(parse_byte_size("2Ki"), Ok(2048));
+        assert_matches!(parse_byte_size(" 1 GiB "), Ok(1_073_741_824));
+    }
+
+    #[test]
+    fn assert_fractions() {
+        assert_matches!(parse_byte_size("1.5KiB"), Ok(1536));
+        assert_matches!(parse_byte_size("0.5kB"), Ok(500));
+        assert_matches!(parse_byte_size(".25KiB"), Ok(256));
+        assert_matches!(parse_byte_size("1.5"), Ok(1));
+        assert_matches!(parse_byte_size("1.0001kB"), Ok(1000));
+    }
+
+    #[test]
+    fn assert_invalid() {
+        assert_matches!(parse_byte_size(""), Err(ByteSizeError::InvalidNumber));
+        assert_matches!(parse_byte_size("MiB"), Err(ByteSizeError::InvalidNumber));
+        assert_matches!(parse_byte_size("1.2.3"), Err(ByteSizeError::InvalidNumber));
+        assert_matches!(parse_byte_size("."), Err(ByteSizeError::InvalidNumber));
+        assert_matches!(
+            parse_byte_size("12 apples"),
+            Err(ByteSizeError::UnknownUnit)
+        );
+    }
+
+    #[test]
+    fn assert_error_hides_value() {
+        let msg = parse_byte_size("12 apples").unwrap_err().to_string();
+        assert_eq!(
+            msg,
+            "unknown byte size unit (expected e.g. `B`, `kB`, `MB`, `KiB`, `MiB`)"
+        );
+        let msg = parse_byte_size("1.2.3").unwrap_err().to_string();
+        assert_eq!(msg, "invalid byte size number");
+    }
+
+    #[test]
+    fn assert_overflow() {
+        assert_matches!(parse_byte_size("18446744073709551615"), Ok(u64::MAX));
+        assert_matches!(
+            parse_byte_size("18446744073709551616"),
+            Err(ByteSizeError::Overflow)
+        );
+        assert_matches!(parse_byte_size("16EiB"), Err(ByteSizeError::Overflow));
+        assert_matches!(
+            parse_byte_size("99999999999999999999999999999999999999999"),
+            Err(ByteSizeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn assert_layer_overflow_error() {
+        const VAR_NAME: &str = "__TEST_BYTE_SIZE_OVERFLOW";
+
+        let config = TextVar::from_var_name(VAR_NAME).byte_size();
+
+        fn is_overflow(e: &(dyn Error + 'static)) -> bool {
+            matches!(e.downcast_ref(), Some(ByteSizeError::Overflow))
+        }
+
+        let res = with_env([(VAR_NAME, "20EB")], || config.try_get());
+        assert_matches!(res, Err(ReadVarError::Other(e)) if is_overflow(&*e));
+    }
+
+    #[test]
+    fn assert_layer_success() {
+        const VAR_NAME: &str = "__TEST_BYTE_SIZE_SUCCESS";
+
+        let config = TextVar::from_var_name(VAR_NAME).byte_size();
+
+        let res = with_env([(VAR_NAME, "4GB")], || config.try_get());
+        assert_matches!(res, Ok(4_000_000_000));
+    }
+}
@@ -1,11 +1,12 @@
 //! Module containing everything related to the [`Cached`] configuration value type.
 
-use std::{fmt, sync::OnceLock};
+use std::{error::Error, fmt, sync::OnceLock};
 
 use crate::{
-    descriptor::{ConfigValueDescriptor, VarDescriptor},
-    error::CachedError,
-    layer::Layer,
+    descriptor::{ConfigValueDescriptor, DescribeLayer, LayerDescription, VarDescriptor},
+    error::{CachedError, LayerError},
+    layer::{Layer, ReadTrace},
+    report::Explanation,
 };
 
 /// A cached configuration value.
@@ -39,9 +40,11 @@ where
     V: Layer,
 {
     pub(crate) var: V,
-    pub(crate) cached: OnceLock<Result<<V as Layer>::Output, <V as Layer>::Error>>,
+    pub(crate) cached: OnceLock<CachedResult<V>>,
 }
 
+type CachedResult<V> = (Result<<V as Layer>::Output, <V as Layer>::Error>, ReadTrace);
+
 impl<V: Layer> Cached<V> {
     /// Same as [`Layer::try_get`], re-declared for more convenience with references.
     #[inline(always)]
@@ -59,11 +62,24 @@ impl<V: Layer> Cached<V> {
         <&Self as Layer>::get(&self)
     }
 
+    /// Same as [`LayerExt::explain`][1], re-declared because the cached configuration value is
+    /// only a layer by reference.
+    ///
+    /// [1]: crate::builder::LayerExt::explain
+    pub fn explain(&self) -> Explanation<'_>
+    where
+        for<'a> &'a Self: Layer<Error: LayerError>,
+        for<'a> Box<dyn Error + Send + Sync + 'a>: From<<&'a Self as Layer>::Error>,
+        Self: ConfigValueDescriptor + DescribeLayer,
+    {
+        Explanation::from_config(self)
+    }
+
     /// Takes the ownership of the cached result.
     ///
     /// It returns `None` if the configuration value hasn't been read yet.
     pub fn take(&mut self) -> Option<Result<<V as Layer>::Output, <V as Layer>::Error>> {
-        self.cached.take()
+        self.cached.take().map(|(res, _)| res)
     }
 }
 
@@ -74,6 +90,13 @@ impl<V: Layer + ConfigValueDescriptor> ConfigValueDescriptor for Cached<V> {
     }
 }
 
+impl<V: Layer + DescribeLayer> DescribeLayer for Cached<V> {
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        desc.push::<<V as Layer>::Output>("cached");
+    }
+}
+
 impl<'a, V> Layer for &'a Cached<V>
 where
     V: Layer,
@@ -82,10 +105,16 @@ where
     type Error = CachedError<'a, <V as Layer>::Error>;
 
     fn try_get(&self) -> Result<Self::Output, Self::Error> {
-        self.cached
-            .get_or_init(|| self.var.try_get())
-            .as_ref()
-            .map_err(CachedError)
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        let (res, cached_trace) = self.cached.get_or_init(|| {
+            let mut trace = ReadTrace::new();
+            (self.var.try_get_traced(&mut trace), trace)
+        });
+        trace.extend(cached_trace);
+        res.as_ref().map_err(CachedError)
     }
 }
 
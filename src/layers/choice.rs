@@ -0,0 +1,100 @@
+//! Module containing everything related to the [`Choice`] configuration value type.
+
+use crate::{
+    descriptor::{
+        ConfigValueDescriptor, Constraint, DescribeLayer, LayerDescription, VarDescriptor,
+    },
+    error::{ChoiceError, ReadVarError},
+    layer::{Layer, ReadTrace},
+};
+
+/// A configuration value that checks that the content of the inner configuration value is one
+/// of the allowed values.
+///
+/// The values are compared exactly, i.e. case-sensitively.
+///
+/// To construct it, see [`one_of`][1].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::prelude::*;
+/// # unsafe { std::env::set_var("LOG_FORMAT", "xml"); }
+/// let my_config = TextVar::from_var_name("LOG_FORMAT").one_of(&["json", "text"]);
+/// let res = my_config.try_get();
+/// # unsafe { std::env::remove_var("LOG_FORMAT"); }
+/// assert!(res.is_err());
+/// ```
+///
+/// [1]: crate::builder::LayerExt::one_of
+pub struct Choice<V> {
+    pub(crate) var: V,
+    pub(crate) choices: &'static [&'static str],
+}
+
+impl<V: ConfigValueDescriptor> ConfigValueDescriptor for Choice<V> {
+    #[inline]
+    fn get_descriptor(&self) -> &VarDescriptor {
+        self.var.get_descriptor()
+    }
+}
+
+impl<V: Layer + DescribeLayer> DescribeLayer for Choice<V> {
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        desc.push::<<V as Layer>::Output>("choice");
+        desc.push_constraint(Constraint::OneOf {
+            values: self.choices,
+        });
+    }
+}
+
+impl<V> Layer for Choice<V>
+where
+    V: Layer<Output: AsRef<str>>,
+    ReadVarError: From<<V as Layer>::Error>,
+{
+    type Output = <V as Layer>::Output;
+    type Error = ReadVarError;
+
+    fn try_get(&self) -> Result<Self::Output, Self::Error> {
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        let val = self.var.try_get_traced(trace)?;
+        if self.choices.contains(&val.as_ref()) {
+            Ok(val)
+        } else {
+            Err(ReadVarError::other(ChoiceError {
+                choices: self.choices,
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{ErrorKind, LayerError},
+        prelude::*,
+        tests::{assert_matches, with_env},
+    };
+
+    #[test]
+    fn assert_choice() {
+        const VAR_NAME: &str = "__TEST_CHOICE";
+
+        let config = TextVar::from_var_name(VAR_NAME).one_of(&["json", "text"]);
+
+        let res = with_env([(VAR_NAME, "json")], || config.try_get());
+        assert_matches!(res.as_deref(), Ok("json"));
+
+        let res = with_env([(VAR_NAME, "JSON")], || config.try_get());
+        assert_matches!(
+            res,
+            Err(e) if e.kind() == ErrorKind::Invalid
+                && e.to_string() == "invalid value (expected one of `json`, `text`)"
+        );
+    }
+}
@@ -3,9 +3,11 @@
 use std::path::Path;
 
 use crate::{
-    descriptor::{ConfigValueDescriptor, VarDescriptor},
+    descriptor::{
+        ConfigValueDescriptor, Constraint, DescribeLayer, LayerDescription, VarDescriptor,
+    },
     error::ReadVarError,
-    layer::Layer,
+    layer::{Layer, ReadStep, ReadTrace},
 };
 
 /// A configuration value that reads the content of the specified file.
@@ -42,6 +44,14 @@ where
     }
 }
 
+impl<V: DescribeLayer> DescribeLayer for FileRead<V> {
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        desc.push::<String>("file content");
+        desc.push_constraint(Constraint::FilePath);
+    }
+}
+
 impl<V> Layer for FileRead<V>
 where
     V: Layer,
@@ -52,8 +62,16 @@ where
     type Error = ReadVarError;
 
     fn try_get(&self) -> Result<Self::Output, Self::Error> {
-        let path = self.var.try_get()?;
-        std::fs::read_to_string(path.as_ref()).map_err(|e| ReadVarError::Other(Box::new(e)))
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        let path = self.var.try_get_traced(trace)?;
+        let path = path.as_ref();
+        trace.record_step(ReadStep::File {
+            path: path.to_path_buf(),
+        });
+        std::fs::read_to_string(path).map_err(ReadVarError::other)
     }
 }
 
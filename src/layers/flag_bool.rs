@@ -0,0 +1,181 @@
+//! Module containing everything related to the [`FlagBool`] configuration value type.
+
+use std::env::VarError;
+
+use crate::{
+    descriptor::{
+        ConfigValueDescriptor, Constraint, DescribeLayer, LayerDescription, VarDescriptor,
+    },
+    error::{FlagBoolError, ReadVarError},
+    layer::{Layer, ReadTrace, ReadWarning},
+};
+
+/// The spellings accepted as `true`, compared case-insensitively.
+pub const TRUTHY_VALUES: &[&str] = &["1", "true", "t", "yes", "y", "on"];
+
+/// The spellings accepted as `false`, compared case-insensitively.
+pub const FALSY_VALUES: &[&str] = &["0", "false", "f", "no", "n", "off"];
+
+/// A configuration value that reads a boolean flag from the content of the inner configuration
+/// value.
+///
+/// By default, the content is parsed leniently: it must be one of the [truthy][1] or
+/// [falsy][2] spellings, compared case-insensitively and ignoring surrounding whitespace.
+///
+/// In presence-only mode, the flag is `true` as soon as the environment variable is set, even
+/// to an empty value, and `false` if it isn't set.
+///
+/// To construct it, see [`flag_bool`][3] and [`flag_present`][4].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::prelude::*;
+/// # unsafe { std::env::set_var("VERBOSE", "Yes"); }
+/// let my_config = TextVar::from_var_name("VERBOSE").flag_bool();
+/// let res = my_config.try_get();
+/// # unsafe { std::env::remove_var("VERBOSE"); }
+/// assert_eq!(res, Ok(true));
+/// ```
+///
+/// [1]: TRUTHY_VALUES
+/// [2]: FALSY_VALUES
+/// [3]: crate::builder::LayerExt::flag_bool
+/// [4]: crate::builder::LayerExt::flag_present
+pub struct FlagBool<V> {
+    pub(crate) var: V,
+    pub(crate) presence_only: bool,
+}
+
+impl<V: ConfigValueDescriptor> ConfigValueDescriptor for FlagBool<V> {
+    #[inline]
+    fn get_descriptor(&self) -> &VarDescriptor {
+        self.var.get_descriptor()
+    }
+}
+
+impl<V: DescribeLayer> DescribeLayer for FlagBool<V> {
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        if self.presence_only {
+            desc.push::<bool>("presence flag");
+            desc.set_optional();
+        } else {
+            desc.push::<bool>("flag");
+            desc.push_constraint(Constraint::Flag);
+        }
+    }
+}
+
+impl<V> Layer for FlagBool<V>
+where
+    V: Layer<Output: AsRef<str>>,
+    ReadVarError: From<<V as Layer>::Error>,
+{
+    type Output = bool;
+    type Error = ReadVarError;
+
+    fn try_get(&self) -> Result<Self::Output, Self::Error> {
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        let res = self.var.try_get_traced(trace).map_err(ReadVarError::from);
+
+        if self.presence_only {
+            // An empty value is the usual way to set a flag in this mode.
+            trace.remove_warning(&ReadWarning::EmptyValue);
+            return match res {
+                Ok(_) | Err(ReadVarError::Var(VarError::NotUnicode(_))) => Ok(true),
+                Err(ReadVarError::Var(VarError::NotPresent)) => Ok(false),
+                Err(e) => Err(e),
+            };
+        }
+
+        parse_flag_bool(res?.as_ref()).map_err(ReadVarError::other)
+    }
+}
+
+/// Parses the provided input into a boolean, using the lenient spellings.
+pub(crate) fn parse_flag_bool(input: &str) -> Result<bool, FlagBoolError> {
+    let input = input.trim();
+    let matches = |values: &[&str]| values.iter().any(|v| v.eq_ignore_ascii_case(input));
+
+    if matches(TRUTHY_VALUES) {
+        Ok(true)
+    } else if matches(FALSY_VALUES) {
+        Ok(false)
+    } else {
+        Err(FlagBoolError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use crate::{
+        error::{FlagBoolError, ReadVarError},
+        prelude::*,
+        tests::{assert_matches, with_env},
+    };
+
+    use super::parse_flag_bool;
+
+    #[test]
+    fn assert_spellings() {
+        for input in ["1", "true", "TRUE", "t", "yes", "Yes", "Y", "on", " On "] {
+            assert_matches!(parse_flag_bool(input), Ok(true));
+        }
+        for input in ["0", "false", "False", "f", "no", "NO", "n", "off", "OFF"] {
+            assert_matches!(parse_flag_bool(input), Ok(false));
+        }
+        for input in ["", "2", "yep", "enabled"] {
+            assert_matches!(parse_flag_bool(input), Err(FlagBoolError { .. }));
+        }
+    }
+
+    #[test]
+    fn assert_error_lists_spellings() {
+        let msg = parse_flag_bool("maybe").unwrap_err().to_string();
+        assert!(!msg.contains("maybe"));
+        assert!(msg.contains("`yes`"));
+        assert!(msg.contains("`off`"));
+    }
+
+    #[test]
+    fn assert_flag_bool_layer() {
+        const VAR_NAME: &str = "__TEST_FLAG_BOOL";
+
+        let config = TextVar::from_var_name(VAR_NAME).flag_bool();
+
+        fn is_flag_bool_error(e: &(dyn Error + 'static)) -> bool {
+            e.downcast_ref::<FlagBoolError>().is_some()
+        }
+
+        let res = with_env([(VAR_NAME, "on")], || config.try_get());
+        assert_matches!(res, Ok(true));
+
+        let res = with_env([(VAR_NAME, "nope")], || config.try_get());
+        assert_matches!(res, Err(ReadVarError::Other(e)) if is_flag_bool_error(&*e));
+
+        let res = with_env([], || config.try_get());
+        assert_matches!(res, Err(ReadVarError::Var(_)));
+    }
+
+    #[test]
+    fn assert_flag_present_layer() {
+        const VAR_NAME: &str = "__TEST_FLAG_PRESENT";
+
+        let config = TextVar::from_var_name(VAR_NAME).flag_present();
+
+        let res = with_env([(VAR_NAME, "")], || config.try_get());
+        assert_matches!(res, Ok(true));
+
+        let res = with_env([(VAR_NAME, "false")], || config.try_get());
+        assert_matches!(res, Ok(true));
+
+        let res = with_env([], || config.try_get());
+        assert_matches!(res, Ok(false));
+    }
+}
@@ -0,0 +1,155 @@
+//! Module containing everything related to the [`InRange`] configuration value type.
+
+use std::{fmt, ops::Bound};
+
+use crate::{
+    descriptor::{
+        ConfigValueDescriptor, Constraint, DescribeLayer, LayerDescription, VarDescriptor,
+    },
+    error::{RangeError, ReadVarError},
+    layer::{Layer, ReadTrace},
+};
+
+/// A configuration value that checks that the output of the inner configuration value is in
+/// the provided range.
+///
+/// To construct it, see [`in_range`][1].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::prelude::*;
+/// # unsafe { std::env::set_var("WORKERS", "0"); }
+/// let my_config = TextVar::from_var_name("WORKERS")
+///   .parsed_from_str::<u8>()
+///   .in_range(1..=16);
+/// let res = my_config.try_get();
+/// # unsafe { std::env::remove_var("WORKERS"); }
+/// assert!(res.is_err());
+/// ```
+///
+/// [1]: crate::builder::LayerExt::in_range
+pub struct InRange<V: Layer> {
+    pub(crate) var: V,
+    pub(crate) start: Bound<<V as Layer>::Output>,
+    pub(crate) end: Bound<<V as Layer>::Output>,
+}
+
+impl<V: Layer + ConfigValueDescriptor> ConfigValueDescriptor for InRange<V> {
+    #[inline]
+    fn get_descriptor(&self) -> &VarDescriptor {
+        self.var.get_descriptor()
+    }
+}
+
+impl<V> DescribeLayer for InRange<V>
+where
+    V: Layer<Output: fmt::Display> + DescribeLayer,
+{
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        let constraint = Constraint::Range {
+            start: self.start.as_ref().map(ToString::to_string),
+            end: self.end.as_ref().map(ToString::to_string),
+        };
+        desc.push::<<V as Layer>::Output>(format!("range {constraint}"));
+        desc.push_constraint(constraint);
+    }
+}
+
+impl<V> Layer for InRange<V>
+where
+    V: Layer<Output: PartialOrd + fmt::Display>,
+    ReadVarError: From<<V as Layer>::Error>,
+{
+    type Output = <V as Layer>::Output;
+    type Error = ReadVarError;
+
+    fn try_get(&self) -> Result<Self::Output, Self::Error> {
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        let val = self.var.try_get_traced(trace)?;
+        let after_start = match &self.start {
+            Bound::Included(start) => val >= *start,
+            Bound::Excluded(start) => val > *start,
+            Bound::Unbounded => true,
+        };
+        let before_end = match &self.end {
+            Bound::Included(end) => val <= *end,
+            Bound::Excluded(end) => val < *end,
+            Bound::Unbounded => true,
+        };
+
+        if after_start && before_end {
+            Ok(val)
+        } else {
+            Err(ReadVarError::other(RangeError {
+                start: self.start.as_ref().map(ToString::to_string),
+                end: self.end.as_ref().map(ToString::to_string),
+            }))
+        }
+    }
+}
+
+/// Writes the bounds of a range, e.g. `>= 1 and < 10`.
+pub(crate) fn fmt_bounds(
+    f: &mut fmt::Formatter<'_>,
+    start: &Bound<String>,
+    end: &Bound<String>,
+) -> fmt::Result {
+    let start = match start {
+        Bound::Included(start) => Some((">=", start)),
+        Bound::Excluded(start) => Some((">", start)),
+        Bound::Unbounded => None,
+    };
+    let end = match end {
+        Bound::Included(end) => Some(("<=", end)),
+        Bound::Excluded(end) => Some(("<", end)),
+        Bound::Unbounded => None,
+    };
+
+    match (start, end) {
+        (Some((op, start)), Some((end_op, end))) => write!(f, "{op} {start} and {end_op} {end}"),
+        (Some((op, bound)), None) | (None, Some((op, bound))) => write!(f, "{op} {bound}"),
+        (None, None) => f.write_str("any value"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{ErrorKind, LayerError},
+        prelude::*,
+        tests::{assert_matches, with_env},
+    };
+
+    #[test]
+    fn assert_in_range() {
+        const VAR_NAME: &str = "__TEST_IN_RANGE";
+
+        let config = TextVar::from_var_name(VAR_NAME)
+            .parsed_from_str::<i32>()
+            .in_range(1..10);
+
+        let res = with_env([(VAR_NAME, "1")], || config.try_get());
+        assert_matches!(res, Ok(1));
+
+        let res = with_env([(VAR_NAME, "10")], || config.try_get());
+        assert_matches!(
+            res,
+            Err(e) if e.kind() == ErrorKind::Invalid
+                && e.to_string() == "value out of range (expected >= 1 and < 10)"
+        );
+
+        let config = TextVar::from_var_name(VAR_NAME)
+            .parsed_from_str::<f64>()
+            .in_range(..=0.5);
+        let res = with_env([(VAR_NAME, "0.75")], || config.try_get());
+        assert_matches!(
+            res,
+            Err(e) if e.to_string() == "value out of range (expected <= 0.5)"
+        );
+    }
+}
@@ -4,14 +4,26 @@
 //!
 //! [1]: crate::layer::Layer
 
+pub mod byte_size;
 pub mod cached;
+pub mod choice;
 pub mod file_read;
+pub mod flag_bool;
+pub mod in_range;
+pub mod optional;
 pub mod or_default;
 pub mod parsed;
 pub mod text_var;
+pub mod typed_parsed;
 
+pub use byte_size::ByteSize;
 pub use cached::Cached;
+pub use choice::Choice;
 pub use file_read::FileRead;
-pub use or_default::OrDefault;
+pub use flag_bool::FlagBool;
+pub use in_range::InRange;
+pub use optional::Optional;
+pub use or_default::{OrDefault, OrDefaultStrict};
 pub use parsed::Parsed;
 pub use text_var::TextVar;
+pub use typed_parsed::TypedParsed;
@@ -0,0 +1,146 @@
+//! Module containing everything related to the [`Optional`] configuration value type.
+
+use std::env::VarError;
+
+use crate::{
+    descriptor::{ConfigValueDescriptor, DescribeLayer, LayerDescription, VarDescriptor},
+    error::ReadVarError,
+    layer::{Layer, ReadTrace},
+};
+
+/// A configuration value that is allowed to be missing from the environment.
+///
+/// It returns `None` only if the environment variable isn't set. Unlike [`OrDefault`][1], any
+/// other error, such as an invalid value, is still returned.
+///
+/// To construct it, see [`optional`][2].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::prelude::*;
+/// let my_config = TextVar::from_var_name("OPTIONAL_PORT")
+///   .parsed_from_str::<u16>()
+///   .optional();
+/// let res = my_config.try_get();
+/// assert_eq!(res, Ok(None));
+/// ```
+///
+/// [1]: crate::layers::OrDefault
+/// [2]: crate::builder::LayerExt::optional
+pub struct Optional<V> {
+    pub(crate) var: V,
+}
+
+impl<V: ConfigValueDescriptor> ConfigValueDescriptor for Optional<V> {
+    #[inline]
+    fn get_descriptor(&self) -> &VarDescriptor {
+        self.var.get_descriptor()
+    }
+}
+
+impl<V: Layer + DescribeLayer> DescribeLayer for Optional<V> {
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        desc.push::<Option<<V as Layer>::Output>>("optional");
+        desc.set_optional();
+    }
+}
+
+impl<V> Layer for Optional<V>
+where
+    V: Layer,
+    ReadVarError: From<<V as Layer>::Error>,
+{
+    type Output = Option<<V as Layer>::Output>;
+    type Error = ReadVarError;
+
+    fn try_get(&self) -> Result<Self::Output, Self::Error> {
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        match self.var.try_get_traced(trace).map_err(ReadVarError::from) {
+            Ok(val) => Ok(Some(val)),
+            Err(ReadVarError::Var(VarError::NotPresent)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::ReadVarError,
+        exec::ConfigInitializer,
+        prelude::*,
+        tests::{assert_matches, with_env},
+    };
+
+    #[test]
+    fn assert_missing() {
+        const VAR_NAME: &str = "__TEST_OPTIONAL_MISSING";
+
+        let config = TextVar::from_var_name(VAR_NAME)
+            .parsed_from_str::<u16>()
+            .optional();
+
+        let res = with_env([], || config.try_get());
+        assert_matches!(res, Ok(None));
+    }
+
+    #[test]
+    fn assert_present() {
+        const VAR_NAME: &str = "__TEST_OPTIONAL_PRESENT";
+
+        let config = TextVar::from_var_name(VAR_NAME)
+            .parsed_from_str::<u16>()
+            .optional();
+
+        let res = with_env([(VAR_NAME, "80")], || config.try_get());
+        assert_matches!(res, Ok(Some(80)));
+    }
+
+    #[test]
+    fn assert_invalid() {
+        const VAR_NAME: &str = "__TEST_OPTIONAL_INVALID";
+
+        let config = TextVar::from_var_name(VAR_NAME)
+            .parsed_from_str::<u16>()
+            .optional();
+
+        let res = with_env([(VAR_NAME, "80a")], || config.try_get());
+        assert_matches!(res, Err(ReadVarError::Other(_)));
+    }
+
+    #[test]
+    fn assert_init_surfaces_invalid() {
+        crate::make_config! {
+            struct TestConfig {
+                port: {
+                    var_name: "__TEST_OPTIONAL_INIT_PORT",
+                    layers: [parsed_from_str<u16>(), optional()],
+                },
+                timeout: {
+                    var_name: "__TEST_OPTIONAL_INIT_TIMEOUT",
+                    layers: [parsed_from_str<u64>(), or_default_val_strict(|| 30)],
+                },
+            }
+        }
+
+        let config = TestConfig::define();
+
+        let res = with_env([], || config.try_init().is_ok());
+        assert!(res);
+
+        let res = with_env([("__TEST_OPTIONAL_INIT_PORT", "80a")], || {
+            config.try_init().is_ok()
+        });
+        assert!(!res);
+
+        let res = with_env([("__TEST_OPTIONAL_INIT_TIMEOUT", "30s")], || {
+            config.try_init().is_ok()
+        });
+        assert!(!res);
+    }
+}
@@ -1,17 +1,107 @@
-//! Module containing everything related to the [`OrDefault`] configuration value type.
+//! Module containing everything related to the [`OrDefault`] and [`OrDefaultStrict`]
+//! configuration value types.
 
-use std::convert::Infallible;
+use std::{borrow::Cow, convert::Infallible, env::VarError, fmt};
 
 use crate::{
-    descriptor::{ConfigValueDescriptor, VarDescriptor},
-    layer::Layer,
+    descriptor::{ConfigValueDescriptor, DescribeLayer, LayerDescription, VarDescriptor},
+    error::{LayerError, ReadVarError},
+    layer::{DefaultCause, Layer, ReadTrace, ReadWarning},
 };
 
+/// The type of the default value function.
+pub type DefaultFn<T> = fn() -> T;
+
+/// The type of a boxed default value function, able to capture its environment.
+///
+/// This is the type used by the `or_default_with` and `or_default_with_strict` layers of the
+/// [`make_config!`][1] macro.
+///
+/// [1]: crate::make_config
+pub type BoxedDefaultFn<T> = Box<dyn Fn() -> T + Send + Sync>;
+
+/// The type of the function comparing the read value with the default value.
+type EqFn<T> = fn(&T, &T) -> bool;
+
+/// Returns whether the default value must be formatted to be shown in the descriptor, i.e. if it
+/// doesn't show one yet, and the variable isn't secret.
+fn needs_default_fmt(descriptor: &VarDescriptor) -> bool {
+    descriptor.default_val_fmt.is_none() && !descriptor.is_secret()
+}
+
+/// Returns a copy of the descriptor showing the provided default value, if it needs one.
+fn with_default_fmt(
+    descriptor: &VarDescriptor,
+    default_val_fmt: Option<String>,
+) -> Option<VarDescriptor> {
+    default_val_fmt
+        .filter(|_| needs_default_fmt(descriptor))
+        .map(|default_val_fmt| VarDescriptor {
+            default_val_fmt: Some(Cow::Owned(default_val_fmt)),
+            ..descriptor.clone()
+        })
+}
+
+/// Returns the text of the layer step, with the default value unless the variable is secret.
+fn default_step(descriptor: &VarDescriptor, suffix: &str) -> Cow<'static, str> {
+    match descriptor.default_val_fmt() {
+        Some(default_val) if !descriptor.is_secret() => {
+            format!("default {default_val}{suffix}").into()
+        }
+        _ => format!("default{suffix}").into(),
+    }
+}
+
+/// Formats the default value of the [`make_config!`](crate::make_config) macro layers with the
+/// most suitable trait, using autoref specialization: `Display`, then `Debug`, then nothing.
+#[doc(hidden)]
+pub struct DefaultValFmt<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait FmtDefaultDisplay {
+    fn __fmt_default(&self) -> Option<String>;
+}
+
+impl<T: fmt::Display> FmtDefaultDisplay for &&DefaultValFmt<'_, T> {
+    fn __fmt_default(&self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+#[doc(hidden)]
+pub trait FmtDefaultDebug {
+    fn __fmt_default(&self) -> Option<String>;
+}
+
+impl<T: fmt::Debug> FmtDefaultDebug for &DefaultValFmt<'_, T> {
+    fn __fmt_default(&self) -> Option<String> {
+        Some(format!("{:?}", self.0))
+    }
+}
+
+#[doc(hidden)]
+pub trait FmtDefaultNone {
+    fn __fmt_default(&self) -> Option<String>;
+}
+
+impl<T> FmtDefaultNone for DefaultValFmt<'_, T> {
+    fn __fmt_default(&self) -> Option<String> {
+        None
+    }
+}
+
 /// Reads the inner configuration value, or returns a default value.
 ///
-/// The read of this type can never fail.
+/// The default value function is a function pointer by default, but it may be any closure, e.g.
+/// to capture a value computed at startup.
 ///
-/// To construct it, see [`or_default_val`][1].
+/// The read of this type can never fail. This means that the default value is also used when
+/// the environment variable is set, but invalid. If you wish to only fallback when the
+/// environment variable isn't set, see [`OrDefaultStrict`]. The error of the inner configuration
+/// value must implement [`LayerError`], so the [trace](ReadTrace) records the kind of the error
+/// that made it use the default value.
+///
+/// To construct it, see [`or_default_val`][1] and [`or_default_with`][2].
 ///
 /// ## Example
 ///
@@ -24,32 +114,339 @@ use crate::{
 /// ```
 ///
 /// [1]: crate::builder::LayerExt::or_default_val
-pub struct OrDefault<V: Layer> {
+/// [2]: crate::builder::LayerExt::or_default_with
+pub struct OrDefault<V: Layer, F = DefaultFn<<V as Layer>::Output>> {
     pub(crate) var: V,
-    pub(crate) default_fn: fn() -> <V as Layer>::Output,
+    pub(crate) default_fn: F,
+    pub(crate) eq_fn: Option<EqFn<<V as Layer>::Output>>,
+    pub(crate) descriptor: Option<VarDescriptor>,
+}
+
+impl<V: Layer, F> OrDefault<V, F> {
+    /// Shows the default value in the configuration descriptor, unless a text was already
+    /// provided with [`default_fmt_val`](crate::layers::TextVar::default_fmt_val), or the
+    /// variable is [secret](crate::layers::TextVar::secret).
+    ///
+    /// The [`make_config!`](crate::make_config) macro does it automatically, with the
+    /// [`Debug`](fmt::Debug) implementation if the output doesn't implement `Display`. The
+    /// builder methods can't: they're generic over the output type, so they can't pick the
+    /// implementation to use on stable Rust, and requiring one would reject the other types.
+    /// See [`debug_default`](OrDefault::debug_default) for the types only implementing `Debug`.
+    ///
+    /// Note: the default value function is called once by this method, unless the default value
+    /// isn't shown.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use mkenv::prelude::*;
+    /// let my_config = TextVar::from_var_name("PORT")
+    ///   .parsed_from_str::<u16>()
+    ///   .or_default_val(|| 8080)
+    ///   .display_default();
+    /// assert_eq!(my_config.get_descriptor().default_val_fmt(), Some("8080"));
+    /// ```
+    pub fn display_default(self) -> Self
+    where
+        V: ConfigValueDescriptor,
+        F: Fn() -> <V as Layer>::Output,
+        <V as Layer>::Output: fmt::Display,
+    {
+        if !needs_default_fmt(self.var.get_descriptor()) {
+            return self;
+        }
+        let default_val_fmt = (self.default_fn)().to_string();
+        self.__default_fmt(Some(default_val_fmt))
+    }
+
+    /// Same as [`display_default`](OrDefault::display_default), but with the
+    /// [`Debug`](fmt::Debug) implementation of the default value.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use mkenv::prelude::*;
+    /// let my_config = TextVar::from_var_name("HOSTS")
+    ///   .parsed(|input| Ok(input.split(',').map(str::to_owned).collect::<Vec<_>>()))
+    ///   .or_default_val(|| vec!["localhost".to_owned()])
+    ///   .debug_default();
+    /// assert_eq!(
+    ///   my_config.get_descriptor().default_val_fmt(),
+    ///   Some(r#"["localhost"]"#),
+    /// );
+    /// ```
+    pub fn debug_default(self) -> Self
+    where
+        V: ConfigValueDescriptor,
+        F: Fn() -> <V as Layer>::Output,
+        <V as Layer>::Output: fmt::Debug,
+    {
+        if !needs_default_fmt(self.var.get_descriptor()) {
+            return self;
+        }
+        let default_val_fmt = format!("{:?}", (self.default_fn)());
+        self.__default_fmt(Some(default_val_fmt))
+    }
+
+    #[doc(hidden)]
+    pub fn __needs_default_fmt(&self) -> bool
+    where
+        V: ConfigValueDescriptor,
+    {
+        needs_default_fmt(self.var.get_descriptor())
+    }
+
+    #[doc(hidden)]
+    pub fn __default_val(&self) -> <V as Layer>::Output
+    where
+        F: Fn() -> <V as Layer>::Output,
+    {
+        (self.default_fn)()
+    }
+
+    #[doc(hidden)]
+    pub fn __default_fmt(mut self, default_val_fmt: Option<String>) -> Self
+    where
+        V: ConfigValueDescriptor,
+    {
+        self.descriptor = with_default_fmt(self.var.get_descriptor(), default_val_fmt);
+        self
+    }
+
+    /// Records a [warning](crate::ReadWarning::SameAsDefault) if the read value is the same as
+    /// the default value.
+    ///
+    /// Note: the default value is then computed on each successful read, to be compared.
+    pub fn warn_if_default(mut self) -> Self
+    where
+        <V as Layer>::Output: PartialEq,
+    {
+        self.eq_fn = Some(PartialEq::eq);
+        self
+    }
 }
 
-impl<V: Layer + ConfigValueDescriptor> ConfigValueDescriptor for OrDefault<V> {
+impl<V: Layer + ConfigValueDescriptor, F> ConfigValueDescriptor for OrDefault<V, F> {
     fn get_descriptor(&self) -> &VarDescriptor {
-        self.var.get_descriptor()
+        self.descriptor
+            .as_ref()
+            .unwrap_or_else(|| self.var.get_descriptor())
     }
 }
 
-impl<V> Layer for OrDefault<V>
+impl<V, F> DescribeLayer for OrDefault<V, F>
 where
-    V: Layer,
+    V: Layer + DescribeLayer + ConfigValueDescriptor,
+{
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        desc.push::<<V as Layer>::Output>(default_step(self.get_descriptor(), ""));
+        desc.set_optional();
+    }
+}
+
+impl<V, F> Layer for OrDefault<V, F>
+where
+    V: Layer<Error: LayerError>,
+    F: Fn() -> <V as Layer>::Output,
 {
     type Output = <V as Layer>::Output;
     type Error = Infallible;
 
     fn try_get(&self) -> Result<Self::Output, Self::Error> {
-        Ok(self.var.try_get().unwrap_or_else(|_| (self.default_fn)()))
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        match self.var.try_get_traced(trace) {
+            Ok(val) => {
+                if let Some(eq_fn) = self.eq_fn
+                    && eq_fn(&val, &(self.default_fn)())
+                {
+                    trace.record_warning(ReadWarning::SameAsDefault);
+                }
+                Ok(val)
+            }
+            Err(e) => {
+                let cause = if trace.var_present() == Some(false) {
+                    DefaultCause::NotPresent
+                } else {
+                    DefaultCause::ReadFailed { kind: e.kind() }
+                };
+                if trace.var_present() == Some(true) {
+                    trace.record_warning(ReadWarning::DefaultOnInvalid);
+                }
+                trace.record_default(cause);
+                Ok((self.default_fn)())
+            }
+        }
+    }
+}
+
+/// Reads the inner configuration value, or returns a default value if the environment variable
+/// isn't set.
+///
+/// Unlike [`OrDefault`], any other error, such as an invalid value, is still returned.
+///
+/// Like [`OrDefault`], the default value function may be any closure.
+///
+/// To construct it, see [`or_default_val_strict`][1] and [`or_default_with_strict`][2].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::prelude::*;
+/// # unsafe { std::env::set_var("STRICT_PORT", "80a"); }
+/// let my_config = TextVar::from_var_name("STRICT_PORT")
+///   .parsed_from_str::<u16>()
+///   .or_default_val_strict(|| 8080);
+/// let res = my_config.try_get();
+/// # unsafe { std::env::remove_var("STRICT_PORT"); }
+/// assert!(res.is_err());
+/// ```
+///
+/// [1]: crate::builder::LayerExt::or_default_val_strict
+/// [2]: crate::builder::LayerExt::or_default_with_strict
+pub struct OrDefaultStrict<V: Layer, F = DefaultFn<<V as Layer>::Output>> {
+    pub(crate) var: V,
+    pub(crate) default_fn: F,
+    pub(crate) eq_fn: Option<EqFn<<V as Layer>::Output>>,
+    pub(crate) descriptor: Option<VarDescriptor>,
+}
+
+impl<V: Layer, F> OrDefaultStrict<V, F> {
+    /// Shows the default value in the configuration descriptor, unless a text was already
+    /// provided with [`default_fmt_val`](crate::layers::TextVar::default_fmt_val), or the
+    /// variable is [secret](crate::layers::TextVar::secret).
+    ///
+    /// See [`OrDefault::display_default`] for more information.
+    pub fn display_default(self) -> Self
+    where
+        V: ConfigValueDescriptor,
+        F: Fn() -> <V as Layer>::Output,
+        <V as Layer>::Output: fmt::Display,
+    {
+        if !needs_default_fmt(self.var.get_descriptor()) {
+            return self;
+        }
+        let default_val_fmt = (self.default_fn)().to_string();
+        self.__default_fmt(Some(default_val_fmt))
+    }
+
+    /// Same as [`display_default`](OrDefaultStrict::display_default), but with the
+    /// [`Debug`](fmt::Debug) implementation of the default value.
+    pub fn debug_default(self) -> Self
+    where
+        V: ConfigValueDescriptor,
+        F: Fn() -> <V as Layer>::Output,
+        <V as Layer>::Output: fmt::Debug,
+    {
+        if !needs_default_fmt(self.var.get_descriptor()) {
+            return self;
+        }
+        let default_val_fmt = format!("{:?}", (self.default_fn)());
+        self.__default_fmt(Some(default_val_fmt))
+    }
+
+    #[doc(hidden)]
+    pub fn __needs_default_fmt(&self) -> bool
+    where
+        V: ConfigValueDescriptor,
+    {
+        needs_default_fmt(self.var.get_descriptor())
+    }
+
+    #[doc(hidden)]
+    pub fn __default_val(&self) -> <V as Layer>::Output
+    where
+        F: Fn() -> <V as Layer>::Output,
+    {
+        (self.default_fn)()
+    }
+
+    #[doc(hidden)]
+    pub fn __default_fmt(mut self, default_val_fmt: Option<String>) -> Self
+    where
+        V: ConfigValueDescriptor,
+    {
+        self.descriptor = with_default_fmt(self.var.get_descriptor(), default_val_fmt);
+        self
+    }
+
+    /// Records a [warning](crate::ReadWarning::SameAsDefault) if the read value is the same as
+    /// the default value.
+    ///
+    /// Note: the default value is then computed on each successful read, to be compared.
+    pub fn warn_if_default(mut self) -> Self
+    where
+        <V as Layer>::Output: PartialEq,
+    {
+        self.eq_fn = Some(PartialEq::eq);
+        self
+    }
+}
+
+impl<V: Layer + ConfigValueDescriptor, F> ConfigValueDescriptor for OrDefaultStrict<V, F> {
+    fn get_descriptor(&self) -> &VarDescriptor {
+        self.descriptor
+            .as_ref()
+            .unwrap_or_else(|| self.var.get_descriptor())
+    }
+}
+
+impl<V, F> DescribeLayer for OrDefaultStrict<V, F>
+where
+    V: Layer + DescribeLayer + ConfigValueDescriptor,
+{
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        desc.push::<<V as Layer>::Output>(default_step(self.get_descriptor(), " if unset"));
+        desc.set_optional();
+    }
+}
+
+impl<V, F> Layer for OrDefaultStrict<V, F>
+where
+    V: Layer,
+    ReadVarError: From<<V as Layer>::Error>,
+    F: Fn() -> <V as Layer>::Output,
+{
+    type Output = <V as Layer>::Output;
+    type Error = ReadVarError;
+
+    fn try_get(&self) -> Result<Self::Output, Self::Error> {
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        match self.var.try_get_traced(trace).map_err(ReadVarError::from) {
+            Ok(val) => {
+                if let Some(eq_fn) = self.eq_fn
+                    && eq_fn(&val, &(self.default_fn)())
+                {
+                    trace.record_warning(ReadWarning::SameAsDefault);
+                }
+                Ok(val)
+            }
+            Err(ReadVarError::Var(VarError::NotPresent)) => {
+                trace.record_default(DefaultCause::NotPresent);
+                Ok((self.default_fn)())
+            }
+            res => res,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{prelude::*, tests::with_env};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{
+        ReadTrace, ReadWarning,
+        error::ReadVarError,
+        prelude::*,
+        tests::{assert_matches, with_env},
+    };
 
     #[test]
     fn assert_default_val() {
@@ -70,4 +467,187 @@ mod tests {
         let Ok(res) = with_env([(VAR_NAME, "hi")], || config.try_get());
         assert_eq!(res, "hi");
     }
+
+    #[test]
+    fn assert_capturing_default_val() {
+        const VAR_NAME: &str = "__TEST_CAPTURING_DEFAULT_VAL";
+
+        let computed = format!("{}-{}", "hello", 42);
+        let config = TextVar::from_var_name(VAR_NAME).or_default_with(move || computed.clone());
+
+        let Ok(res) = with_env([], || config.try_get());
+        assert_eq!(res, "hello-42");
+    }
+
+    #[test]
+    fn assert_strict_default_val() {
+        const VAR_NAME: &str = "__TEST_STRICT_DEFAULT_VAL";
+
+        let config = TextVar::from_var_name(VAR_NAME)
+            .parsed_from_str::<u16>()
+            .or_default_val_strict(|| 8080);
+
+        let res = with_env([], || config.try_get());
+        assert_matches!(res, Ok(8080));
+
+        let res = with_env([(VAR_NAME, "80")], || config.try_get());
+        assert_matches!(res, Ok(80));
+    }
+
+    #[test]
+    fn assert_capturing_strict_default_val() {
+        const VAR_NAME: &str = "__TEST_CAPTURING_STRICT_DEFAULT_VAL";
+
+        let computed = 8000 + 80;
+        let config = TextVar::from_var_name(VAR_NAME)
+            .parsed_from_str::<u16>()
+            .or_default_with_strict(move || computed);
+
+        let res = with_env([], || config.try_get());
+        assert_matches!(res, Ok(8080));
+
+        let res = with_env([(VAR_NAME, "80a")], || config.try_get());
+        assert_matches!(res, Err(ReadVarError::Other(_)));
+    }
+
+    #[test]
+    fn assert_strict_invalid_val() {
+        const VAR_NAME: &str = "__TEST_STRICT_INVALID_VAL";
+
+        let config = TextVar::from_var_name(VAR_NAME)
+            .parsed_from_str::<u16>()
+            .or_default_val_strict(|| 8080);
+
+        let res = with_env([(VAR_NAME, "80a")], || config.try_get());
+        assert_matches!(res, Err(ReadVarError::Other(_)));
+    }
+
+    #[test]
+    fn assert_default_warnings() {
+        const VAR_NAME: &str = "__TEST_DEFAULT_WARNINGS";
+
+        let config = TextVar::from_var_name(VAR_NAME)
+            .parsed_from_str::<u16>()
+            .or_default_val(|| 8080)
+            .warn_if_default();
+
+        let mut trace = ReadTrace::new();
+        let res = with_env([], || config.try_get_traced(&mut trace));
+        assert_matches!(res, Ok(8080));
+        assert!(trace.used_default());
+        assert!(trace.warnings().is_empty());
+
+        let mut trace = ReadTrace::new();
+        let res = with_env([(VAR_NAME, "80a")], || config.try_get_traced(&mut trace));
+        assert_matches!(res, Ok(8080));
+        assert_eq!(trace.warnings(), [ReadWarning::DefaultOnInvalid]);
+
+        let mut trace = ReadTrace::new();
+        let res = with_env([(VAR_NAME, "8080")], || config.try_get_traced(&mut trace));
+        assert_matches!(res, Ok(8080));
+        assert!(!trace.used_default());
+        assert_eq!(trace.warnings(), [ReadWarning::SameAsDefault]);
+    }
+
+    #[test]
+    fn assert_default_val_fmt() {
+        #[derive(Debug)]
+        enum Level {
+            Low,
+        }
+
+        struct Opaque;
+
+        crate::make_config! {
+            struct TestConfig {
+                port: {
+                    var_name: "__TEST_DEFAULT_FMT_PORT",
+                    layers: [parsed_from_str<u16>(), or_default_val(|| 8080)],
+                },
+                timeout: {
+                    var_name: "__TEST_DEFAULT_FMT_TIMEOUT",
+                    default_val_fmt: "30 seconds",
+                    layers: [parsed_from_str<u64>(), or_default_val_strict(|| 30)],
+                },
+                level: {
+                    var_name: "__TEST_DEFAULT_FMT_LEVEL",
+                    layers: [parsed<Level>(|_| Ok(Level::Low)), or_default_val(|| Level::Low)],
+                },
+                opaque: {
+                    var_name: "__TEST_DEFAULT_FMT_OPAQUE",
+                    layers: [parsed<Opaque>(|_| Ok(Opaque)), or_default_val(|| Opaque)],
+                },
+            }
+        }
+
+        let config = TestConfig::define();
+        assert_eq!(config.port.get_descriptor().default_val_fmt(), Some("8080"));
+        assert_eq!(
+            config.timeout.get_descriptor().default_val_fmt(),
+            Some("30 seconds")
+        );
+        assert_eq!(config.level.get_descriptor().default_val_fmt(), Some("Low"));
+        assert_eq!(config.opaque.get_descriptor().default_val_fmt(), None);
+    }
+
+    #[test]
+    fn assert_default_fn_calls() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        fn counted_default() -> u16 {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            8080
+        }
+
+        crate::make_config! {
+            struct TestConfig {
+                shown: {
+                    var_name: "__TEST_DEFAULT_CALLS_SHOWN",
+                    layers: [parsed_from_str<u16>(), or_default_val(counted_default)],
+                },
+                formatted: {
+                    var_name: "__TEST_DEFAULT_CALLS_FORMATTED",
+                    default_val_fmt: "8080",
+                    layers: [parsed_from_str<u16>(), or_default_with(counted_default)],
+                },
+                secret: {
+                    var_name: "__TEST_DEFAULT_CALLS_SECRET",
+                    secret: true,
+                    layers: [parsed_from_str<u16>(), or_default_val_strict(counted_default)],
+                },
+            }
+        }
+
+        let config = TestConfig::define();
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            config.shown.get_descriptor().default_val_fmt(),
+            Some("8080")
+        );
+        assert_eq!(config.secret.get_descriptor().default_val_fmt(), None);
+    }
+
+    #[test]
+    fn assert_debug_default() {
+        #[derive(Debug)]
+        enum Level {
+            Low,
+        }
+
+        let config = TextVar::from_var_name("__TEST_DEBUG_DEFAULT")
+            .parsed(|_| Ok(Level::Low))
+            .or_default_val(|| Level::Low)
+            .debug_default();
+        assert_eq!(config.get_descriptor().default_val_fmt(), Some("Low"));
+    }
+
+    #[test]
+    fn assert_secret_default_step() {
+        let config = TextVar::from_var_name("__TEST_SECRET_DEFAULT_STEP")
+            .secret(true)
+            .or_default_val(|| "hunter2".to_owned())
+            .display_default();
+        assert_eq!(config.get_descriptor().default_val_fmt(), None);
+        assert_eq!(config.describe().to_string(), "text → default");
+    }
 }
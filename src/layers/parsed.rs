@@ -1,19 +1,36 @@
 //! Module containing everything related to the [`Parsed`] configuration value type.
 
-use std::error::Error;
+use std::{any, error::Error, marker::PhantomData};
 
 use crate::{
-    descriptor::{ConfigValueDescriptor, VarDescriptor},
+    descriptor::{
+        ConfigValueDescriptor, Constraint, DescribeLayer, LayerDescription, VarDescriptor,
+        short_type_name,
+    },
     error::{ParseError, ReadVarError},
-    layer::Layer,
+    layer::{Layer, ReadTrace},
 };
 
 /// The type of the parsing function.
-pub type ParseFn<T> = fn(&str) -> Result<T, Box<dyn Error>>;
+pub type ParseFn<T> = fn(&str) -> Result<T, Box<dyn Error + Send + Sync>>;
+
+/// The type of a boxed parsing function, able to capture its environment.
+///
+/// This is the type used by the `parsed_with` layer of the [`make_config!`][1] macro.
+///
+/// [1]: crate::make_config
+pub type BoxedParseFn<T> =
+    Box<dyn Fn(&str) -> Result<T, Box<dyn Error + Send + Sync>> + Send + Sync>;
 
 /// A configuration value that parses the content of the inner configuration value to `T`.
 ///
-/// To construct it, see [`parsed`][1].
+/// The parsing function is a function pointer by default, but it may be any closure, e.g. to
+/// capture runtime data.
+///
+/// The parse error is type-erased in a [`ParseError`]. If you wish to keep its concrete type, see
+/// [`TypedParsed`][3].
+///
+/// To construct it, see [`parsed`][1] and [`parsed_with`][2].
 ///
 /// ## Example
 ///
@@ -28,30 +45,47 @@ pub type ParseFn<T> = fn(&str) -> Result<T, Box<dyn Error>>;
 /// ```
 ///
 /// [1]: crate::builder::LayerExt::parsed
-pub struct Parsed<T, V> {
+/// [2]: crate::builder::LayerExt::parsed_with
+/// [3]: crate::layers::TypedParsed
+pub struct Parsed<T, V, F = ParseFn<T>> {
     pub(crate) var: V,
-    pub(crate) parse_fn: ParseFn<T>,
+    pub(crate) parse_fn: F,
+    pub(crate) _marker: PhantomData<fn() -> T>,
 }
 
-impl<T, V: ConfigValueDescriptor> ConfigValueDescriptor for Parsed<T, V> {
+impl<T, V: ConfigValueDescriptor, F> ConfigValueDescriptor for Parsed<T, V, F> {
     #[inline]
     fn get_descriptor(&self) -> &VarDescriptor {
         self.var.get_descriptor()
     }
 }
 
-impl<T, V> Layer for Parsed<T, V>
+impl<T, V: DescribeLayer, F> DescribeLayer for Parsed<T, V, F> {
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        let type_name = short_type_name(any::type_name::<T>());
+        desc.push::<T>(format!("parse {type_name}"));
+        desc.push_constraint(Constraint::Parsable { type_name });
+    }
+}
+
+impl<T, V, F> Layer for Parsed<T, V, F>
 where
     V: Layer<Output: AsRef<str>>,
     ReadVarError: From<<V as Layer>::Error>,
+    F: Fn(&str) -> Result<T, Box<dyn Error + Send + Sync>>,
 {
     type Output = T;
     type Error = ReadVarError;
 
     fn try_get(&self) -> Result<Self::Output, Self::Error> {
-        let raw_val = self.var.try_get()?;
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        let raw_val = self.var.try_get_traced(trace)?;
         let parse_res = (self.parse_fn)(raw_val.as_ref());
-        parse_res.map_err(|source| ReadVarError::Other(Box::new(ParseError { source })))
+        parse_res.map_err(|source| ReadVarError::other(ParseError { source }))
     }
 }
 
@@ -111,4 +145,16 @@ mod tests {
         let res = with_env([(VAR_NAME, "250")], || config.try_get());
         assert_matches!(res, Ok(d) if d.as_millis() == 250);
     }
+
+    #[test]
+    fn assert_capturing_parse_success() {
+        const VAR_NAME: &str = "__TEST_CAPTURING_PARSE_SUCCESS";
+
+        let base_dir = std::path::PathBuf::from("/srv/app");
+        let config =
+            TextVar::from_var_name(VAR_NAME).parsed_with(move |input| Ok(base_dir.join(input)));
+
+        let res = with_env([(VAR_NAME, "data")], || config.try_get());
+        assert_matches!(res, Ok(p) if p == std::path::Path::new("/srv/app/data"));
+    }
 }
@@ -1,11 +1,14 @@
 //! Module containing everything related to the [`TextVar`] configuration value type.
 
-use std::env;
+use std::{
+    borrow::Cow,
+    env::{self, VarError},
+};
 
 use crate::{
-    descriptor::{ConfigValueDescriptor, VarDescriptor},
+    descriptor::{ConfigValueDescriptor, DescribeLayer, LayerDescription, VarDescriptor},
     error::ReadVarError,
-    layer::Layer,
+    layer::{Layer, ReadTrace, ReadWarning, VarLayer},
 };
 
 /// A configuration value that simply returns the content of the environment variable.
@@ -28,11 +31,7 @@ impl TextVar {
     /// Creates a [`TextVar`] from the environment variable key.
     pub fn from_var_name(var_name: &'static str) -> Self {
         Self {
-            descriptor: VarDescriptor {
-                var_name,
-                description: None,
-                default_val_fmt: None,
-            },
+            descriptor: VarDescriptor::new(var_name),
         }
     }
 
@@ -49,7 +48,71 @@ impl TextVar {
     ///
     /// [1]: crate::builder::LayerExt::or_default_val
     pub fn default_fmt_val(mut self, default_fmt_val: &'static str) -> Self {
-        self.descriptor.default_val_fmt = Some(default_fmt_val);
+        self.descriptor.default_val_fmt = Some(Cow::Borrowed(default_fmt_val));
+        self
+    }
+
+    /// Changes the example value shown in the configuration descriptor.
+    pub fn example(mut self, example: &'static str) -> Self {
+        self.descriptor.example = Some(example);
+        self
+    }
+
+    /// Changes whether the environment variable is required to be set, which is `true` by
+    /// default.
+    ///
+    /// Note: like [`default_fmt_val`](TextVar::default_fmt_val), this is only used as information
+    /// to the user. It is up to you to make the variable optional with e.g.
+    /// [`optional`](crate::builder::LayerExt::optional).
+    pub fn required(mut self, required: bool) -> Self {
+        self.descriptor.required = required;
+        self
+    }
+
+    /// Changes whether the value of the environment variable is a secret, e.g. a password.
+    pub fn secret(mut self, secret: bool) -> Self {
+        self.descriptor.secret = secret;
+        self
+    }
+
+    /// Changes the name of the output type shown in the configuration descriptor.
+    pub fn type_name(mut self, type_name: &'static str) -> Self {
+        self.descriptor.type_name = Some(type_name);
+        self
+    }
+
+    /// Changes the tags of the configuration descriptor.
+    pub fn tags(mut self, tags: &'static [&'static str]) -> Self {
+        self.descriptor.tags = tags;
+        self
+    }
+
+    /// Marks the configuration value as deprecated, with the provided note.
+    ///
+    /// The note is shown in the configuration descriptor, e.g. to tell what to use instead.
+    pub fn deprecated(mut self, note: &'static str) -> Self {
+        self.descriptor.deprecated = Some(note);
+        self
+    }
+
+    /// Adds a deprecated name of the environment variable.
+    ///
+    /// If the environment variable isn't set, the value is read from the deprecated name, and
+    /// a [warning](crate::ReadWarning::DeprecatedAlias) is recorded.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use mkenv::prelude::*;
+    /// # unsafe { std::env::set_var("OLD_DATABASE_URL", "postgres://localhost"); }
+    /// let my_config = TextVar::from_var_name("NEW_DATABASE_URL")
+    ///   .deprecated_alias("OLD_DATABASE_URL");
+    /// let res = my_config.try_get();
+    /// # unsafe { std::env::remove_var("OLD_DATABASE_URL"); }
+    /// assert_eq!(res.as_deref(), Ok("postgres://localhost"));
+    /// ```
+    pub fn deprecated_alias(mut self, alias: &'static str) -> Self {
+        self.descriptor.deprecated_alias = Some(alias);
         self
     }
 }
@@ -61,12 +124,47 @@ impl ConfigValueDescriptor for TextVar {
     }
 }
 
+impl DescribeLayer for TextVar {
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        desc.push::<String>("text");
+    }
+}
+
 impl Layer for TextVar {
     type Output = String;
     type Error = ReadVarError;
 
     fn try_get(&self) -> Result<Self::Output, Self::Error> {
-        env::var(self.descriptor.var_name).map_err(ReadVarError::Var)
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        self.try_get_var_traced(trace).map_err(ReadVarError::Var)
+    }
+}
+
+impl VarLayer for TextVar {
+    fn try_get_var_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, VarError> {
+        let mut res = env::var(self.descriptor.var_name);
+        trace.record_var(
+            self.descriptor.var_name,
+            !matches!(res, Err(VarError::NotPresent)),
+        );
+
+        if let (Err(VarError::NotPresent), Some(alias)) = (&res, self.descriptor.deprecated_alias) {
+            res = env::var(alias);
+            let present = !matches!(res, Err(VarError::NotPresent));
+            trace.record_var(alias, present);
+            if present {
+                trace.record_warning(ReadWarning::DeprecatedAlias { alias });
+            }
+        }
+
+        if matches!(res.as_deref(), Ok("")) {
+            trace.record_warning(ReadWarning::EmptyValue);
+        }
+
+        res
     }
 }
 
@@ -75,6 +173,7 @@ mod tests {
     use std::env::VarError;
 
     use crate::{
+        ReadTrace, ReadWarning,
         error::ReadVarError,
         prelude::*,
         tests::{assert_matches, with_env},
@@ -86,7 +185,7 @@ mod tests {
 
         let config = TextVar::from_var_name(VAR_NAME);
 
-        let res = with_env([], || config.try_get());
+        let res: Result<String, ReadVarError> = with_env([], || config.try_get());
         assert_matches!(res, Err(ReadVarError::Var(VarError::NotPresent)));
     }
 
@@ -99,4 +198,40 @@ mod tests {
         let res = with_env([(VAR_NAME, "hello there")], || config.try_get());
         assert_matches!(res.as_deref(), Ok("hello there"));
     }
+
+    #[test]
+    fn assert_deprecated_alias() {
+        const VAR_NAME: &str = "__TEST_VAR_ALIAS_NEW";
+        const ALIAS: &str = "__TEST_VAR_ALIAS_OLD";
+
+        let config = TextVar::from_var_name(VAR_NAME).deprecated_alias(ALIAS);
+
+        let mut trace = ReadTrace::new();
+        let res = with_env([(ALIAS, "old")], || config.try_get_traced(&mut trace));
+        assert_matches!(res.as_deref(), Ok("old"));
+        assert_eq!(
+            trace.warnings(),
+            [ReadWarning::DeprecatedAlias { alias: ALIAS }]
+        );
+
+        let mut trace = ReadTrace::new();
+        let res = with_env([(VAR_NAME, "new"), (ALIAS, "old")], || {
+            config.try_get_traced(&mut trace)
+        });
+        assert_matches!(res.as_deref(), Ok("new"));
+        assert!(trace.warnings().is_empty());
+    }
+
+    #[test]
+    fn assert_empty_value_warning() {
+        const VAR_NAME: &str = "__TEST_VAR_EMPTY";
+
+        let config = TextVar::from_var_name(VAR_NAME);
+
+        let mut trace = ReadTrace::new();
+        let res = with_env([(VAR_NAME, "")], || config.try_get_traced(&mut trace));
+        assert_matches!(res.as_deref(), Ok(""));
+        assert_eq!(trace.var_present(), Some(true));
+        assert_eq!(trace.warnings(), [ReadWarning::EmptyValue]);
+    }
 }
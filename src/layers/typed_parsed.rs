@@ -0,0 +1,165 @@
+//! Module containing everything related to the [`TypedParsed`] configuration value type.
+
+use std::{any, marker::PhantomData};
+
+use crate::{
+    descriptor::{
+        ConfigValueDescriptor, Constraint, DescribeLayer, LayerDescription, VarDescriptor,
+        short_type_name,
+    },
+    error::ReadVarError,
+    layer::{Layer, ReadTrace, VarLayer},
+};
+
+/// The type of the typed parsing function.
+pub type TypedParseFn<T, E> = fn(&str) -> Result<T, E>;
+
+/// A configuration value that parses the content of the inner configuration value to `T`,
+/// keeping the concrete type `E` of the parse error.
+///
+/// Unlike [`Parsed`][1], the parse error isn't type-erased: the error of the read is a
+/// [`ReadVarError<E>`], so it can be matched on without downcasting. For this reason, the inner
+/// configuration value must only fail because of the environment variable itself, i.e. implement
+/// [`VarLayer`], which is the case of [`TextVar`][2].
+///
+/// To construct it, see [`parsed_typed`][3] and [`parsed_typed_from_str`][4].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::{error::ReadVarError, prelude::*};
+/// # use std::num::IntErrorKind;
+/// # unsafe { std::env::set_var("WORKERS", "-1"); }
+/// let my_config = TextVar::from_var_name("WORKERS")
+///   .parsed_typed_from_str::<u8>();
+/// let res = my_config.try_get();
+/// # unsafe { std::env::remove_var("WORKERS"); }
+/// assert!(matches!(
+///   res,
+///   Err(ReadVarError::Other(e)) if *e.kind() == IntErrorKind::InvalidDigit
+/// ));
+/// ```
+///
+/// [1]: crate::layers::Parsed
+/// [2]: crate::layers::TextVar
+/// [3]: crate::builder::LayerExt::parsed_typed
+/// [4]: crate::builder::LayerExt::parsed_typed_from_str
+pub struct TypedParsed<T, E, V, F = TypedParseFn<T, E>> {
+    pub(crate) var: V,
+    pub(crate) parse_fn: F,
+    pub(crate) _marker: PhantomData<fn() -> Result<T, E>>,
+}
+
+impl<T, E, V: ConfigValueDescriptor, F> ConfigValueDescriptor for TypedParsed<T, E, V, F> {
+    #[inline]
+    fn get_descriptor(&self) -> &VarDescriptor {
+        self.var.get_descriptor()
+    }
+}
+
+impl<T, E, V: DescribeLayer, F> DescribeLayer for TypedParsed<T, E, V, F> {
+    fn describe_layer(&self, desc: &mut LayerDescription) {
+        self.var.describe_layer(desc);
+        let type_name = short_type_name(any::type_name::<T>());
+        desc.push::<T>(format!("parse {type_name}"));
+        desc.push_constraint(Constraint::Parsable { type_name });
+    }
+}
+
+impl<T, E, V, F> Layer for TypedParsed<T, E, V, F>
+where
+    V: VarLayer<Output: AsRef<str>>,
+    F: Fn(&str) -> Result<T, E>,
+{
+    type Output = T;
+    type Error = ReadVarError<E>;
+
+    fn try_get(&self) -> Result<Self::Output, Self::Error> {
+        self.try_get_traced(&mut ReadTrace::new())
+    }
+
+    fn try_get_traced(&self, trace: &mut ReadTrace) -> Result<Self::Output, Self::Error> {
+        let raw_val = self
+            .var
+            .try_get_var_traced(trace)
+            .map_err(ReadVarError::Var)?;
+        (self.parse_fn)(raw_val.as_ref()).map_err(|e| ReadVarError::Other(Box::new(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::VarError,
+        fmt,
+        num::{IntErrorKind, ParseIntError},
+    };
+
+    use crate::{
+        error::ReadVarError,
+        prelude::*,
+        tests::{assert_matches, with_env},
+    };
+
+    #[test]
+    fn assert_parse_success() {
+        const VAR_NAME: &str = "__TEST_TYPED_PARSE_SUCCESS";
+
+        let config = TextVar::from_var_name(VAR_NAME).parsed_typed_from_str::<i32>();
+
+        let res = with_env([(VAR_NAME, "30")], || config.try_get());
+        assert_matches!(res, Ok(30));
+    }
+
+    #[test]
+    fn assert_parse_fail() {
+        const VAR_NAME: &str = "__TEST_TYPED_PARSE_FAIL";
+
+        let config = TextVar::from_var_name(VAR_NAME).parsed_typed_from_str::<i32>();
+
+        let res: Result<i32, ReadVarError<ParseIntError>> =
+            with_env([(VAR_NAME, "foobar")], || config.try_get());
+        assert_matches!(
+            res,
+            Err(ReadVarError::Other(e)) if matches!(e.kind(), IntErrorKind::InvalidDigit)
+        );
+    }
+
+    #[test]
+    fn assert_var_error() {
+        const VAR_NAME: &str = "__TEST_TYPED_PARSE_VAR_ERROR";
+
+        let config = TextVar::from_var_name(VAR_NAME).parsed_typed_from_str::<i32>();
+
+        let res = with_env([], || config.try_get());
+        assert_matches!(res, Err(ReadVarError::Var(VarError::NotPresent)));
+    }
+
+    #[test]
+    fn assert_custom_error() {
+        const VAR_NAME: &str = "__TEST_TYPED_PARSE_CUSTOM_ERROR";
+
+        #[derive(Debug, PartialEq)]
+        enum LevelError {
+            Unknown,
+        }
+
+        impl fmt::Display for LevelError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("unknown level")
+            }
+        }
+
+        let config = TextVar::from_var_name(VAR_NAME).parsed_typed(|input| match input {
+            "low" => Ok(0),
+            "high" => Ok(1),
+            _ => Err(LevelError::Unknown),
+        });
+
+        let res = with_env([(VAR_NAME, "high")], || config.try_get());
+        assert_matches!(res, Ok(1));
+
+        let res = with_env([(VAR_NAME, "medium")], || config.try_get());
+        assert_matches!(res, Err(ReadVarError::Other(e)) if *e == LevelError::Unknown);
+    }
+}
@@ -81,6 +81,15 @@
 //! - `PWD`
 //! ```
 //!
+//! If you'd rather not panic, the [`init_or_exit()`][13] method prints the same message to the
+//! standard error, and exits the process with the conventional `EX_CONFIG` (78) exit code.
+//! The error of the [`try_init()`][14] method can also be converted into an
+//! [`OwnedConfigInitError`][15], to be returned from the `main` function. Wrapped in a
+//! [`MainResult`][16], it makes the process exit with the same `EX_CONFIG` exit code.
+//!
+//! The format of this message can be changed, e.g. to use colors or a compact style, with the
+//! formatters of the [`report`] module.
+//!
 //! ## Features
 //!
 //! ### Layers
@@ -177,6 +186,42 @@
 //! the one of the `DbConfig` struct, meaning all the environment variables the `DbConfig` struct
 //! needs, are also needed by the `AppConfig` struct.
 //!
+//! An environment variable can only be declared once in the whole configuration, including the
+//! nested ones. Otherwise, the compilation fails:
+//!
+//! ```compile_fail
+//! # use mkenv::make_config;
+//! make_config! {
+//!   struct HttpConfig {
+//!     port: { var_name: "PORT" },
+//!   }
+//! }
+//!
+//! make_config! {
+//!   struct AppConfig {
+//!     http: { HttpConfig },
+//!     // error: the environment variable `PORT` is declared by several fields
+//!     metrics_port: { var_name: "PORT" },
+//!   }
+//! }
+//! ```
+//!
+//! The deprecated aliases are declarations too, so they can't be used by another field either:
+//!
+//! ```compile_fail
+//! # use mkenv::make_config;
+//! make_config! {
+//!   struct AppConfig {
+//!     port: { var_name: "PORT" },
+//!     // error: the environment variable `PORT` is declared by several fields
+//!     http_port: { var_name: "HTTP_PORT", deprecated_alias: "PORT" },
+//!   }
+//! }
+//! ```
+//!
+//! For the configurations that aren't generated by the macro, the duplicates are reported by
+//! [`try_init()`][14] instead, with the path of the fields declaring them.
+//!
 //! You may also use the composable pattern for conditional purposes:
 //!
 //! ```no_run
@@ -273,15 +318,24 @@
 //! [10]: crate::layers::Cached
 //! [11]: crate::Layer::try_get
 //! [12]: crate::Layer::get
+//! [13]: crate::exec::ConfigInitializer::init_or_exit
+//! [14]: crate::exec::ConfigInitializer::try_init
+//! [15]: crate::error::OwnedConfigInitError
+//! [16]: crate::error::MainResult
 
 #![cfg_attr(feature = "nightly", feature(doc_notable_trait))]
 
 mod builder;
+mod check;
 mod descriptor;
+mod distance;
+pub mod doc;
 pub mod error;
 pub mod exec;
+mod json;
 mod layer;
 pub mod layers;
+pub mod report;
 
 mod macros;
 
@@ -289,23 +343,30 @@ mod macros;
 pub(crate) mod tests;
 
 pub use builder::LayerExt;
-pub use descriptor::{ConfigDescriptor, ConfigValueDescriptor, VarDescriptor};
-pub use layer::Layer;
+pub use descriptor::{
+    ConfigDescriptor, ConfigValueDescriptor, ConfigVisitor, Constraint, DescribeLayer,
+    LayerDescription, VarDescriptor,
+};
+pub use layer::{DefaultCause, Layer, ReadStep, ReadTrace, ReadWarning, VarLayer};
 
 /// Utility module importing the most relevant types and traits.
 ///
 /// It is meant to be imported like this: `use mkenv::prelude::*;`
 pub mod prelude {
     pub use super::{
-        ConfigDescriptor as _, ConfigValueDescriptor as _, Layer as _, LayerExt as _,
-        exec::ConfigInitializer as _, layers::*,
+        ConfigDescriptor as _, ConfigValueDescriptor as _, DescribeLayer as _, Layer as _,
+        LayerExt as _, exec::ConfigInitializer as _, layers::*,
     };
 }
 
 #[doc(hidden)]
 pub mod __private {
-    pub use super::macros::make_config_impl;
-    pub use std::{fmt, iter};
+    pub use super::{
+        check::{VarNames, check_var_names},
+        layers::or_default::{DefaultValFmt, FmtDefaultDebug, FmtDefaultDisplay, FmtDefaultNone},
+        macros::make_config_impl,
+    };
+    pub use std::{boxed::Box, compile_error, concat, fmt, iter, option::Option, stringify};
 }
 
 #[deprecated(
@@ -9,23 +9,24 @@ macro_rules! make_config_impl {
         $Config
     };
 
-    (@__field_config_ty
-        var_name: $_var_name:literal
-        $(, $($rest:tt)*)?
-    ) => {
-        $crate::__private::make_config_impl!(@__field_config_ty_layers $($($rest)*)?)
+    (@__field_config_ty $($key:ident: $value:tt),+ $(,)?) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layers $($key: $value),+)
     };
 
-    (@__field_config_ty_layers layers: [] $($_rest:tt)*) => {
+    (@__field_config_ty_layers layers: [] $(, $($_rest:tt)*)?) => {
         $crate::layers::TextVar
     };
 
-    (@__field_config_ty_layers layers: [$($layers_tt:tt)+] $($_rest:tt)*) => {
+    (@__field_config_ty_layers layers: [$($layers_tt:tt)+] $(, $($_rest:tt)*)?) => {
         $crate::__private::make_config_impl!(@__field_config_ty_layers_content $($layers_tt)+)
     };
 
+    (@__field_config_ty_layers $_key:ident: $_value:tt $(, $($rest:tt)*)?) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layers $($($rest)*)?)
+    };
+
     // Case where the `layers` key wasn't provided
-    (@__field_config_ty_layers $($_rest:tt)*) => {
+    (@__field_config_ty_layers) => {
         $crate::layers::TextVar
     };
 
@@ -68,6 +69,17 @@ macro_rules! make_config_impl {
         )
     };
 
+    (@__field_config_ty_layer [[parsed_with<$parse_ty:ty>($($_content:tt)*)] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::Parsed<
+                $parse_ty,
+                $($wrapped)*,
+                $crate::layers::parsed::BoxedParseFn<$parse_ty>,
+            >
+        )
+    };
+
     (@__field_config_ty_layer [[parsed_from_str<$parse_ty:ty>()] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
         $crate::__private::make_config_impl!(@__field_config_ty_layer
             [$([$($rest)*])*]
@@ -75,6 +87,41 @@ macro_rules! make_config_impl {
         )
     };
 
+    (@__field_config_ty_layer [[byte_size()] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::ByteSize<$($wrapped)*>
+        )
+    };
+
+    (@__field_config_ty_layer [[one_of($($_content:tt)*)] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::Choice<$($wrapped)*>
+        )
+    };
+
+    (@__field_config_ty_layer [[in_range($($_content:tt)*)] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::InRange<$($wrapped)*>
+        )
+    };
+
+    (@__field_config_ty_layer [[flag_bool()] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::FlagBool<$($wrapped)*>
+        )
+    };
+
+    (@__field_config_ty_layer [[flag_present()] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::FlagBool<$($wrapped)*>
+        )
+    };
+
     (@__field_config_ty_layer [[or_default_val($($_content:tt)*)] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
         $crate::__private::make_config_impl!(@__field_config_ty_layer
             [$([$($rest)*])*]
@@ -89,6 +136,59 @@ macro_rules! make_config_impl {
         )
     };
 
+    (@__field_config_ty_layer [[or_default_with($($_content:tt)*)] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::OrDefault<
+                $($wrapped)*,
+                $crate::layers::or_default::BoxedDefaultFn<
+                    <$($wrapped)* as $crate::Layer>::Output
+                >,
+            >
+        )
+    };
+
+    (@__field_config_ty_layer [[optional()] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::Optional<$($wrapped)*>
+        )
+    };
+
+    (@__field_config_ty_layer [[or_default_val_strict($($_content:tt)*)] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::OrDefaultStrict<$($wrapped)*>
+        )
+    };
+
+    (@__field_config_ty_layer [[or_default_strict()] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::OrDefaultStrict<$($wrapped)*>
+        )
+    };
+
+    (@__field_config_ty_layer [[or_default_with_strict($($_content:tt)*)] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $crate::layers::OrDefaultStrict<
+                $($wrapped)*,
+                $crate::layers::or_default::BoxedDefaultFn<
+                    <$($wrapped)* as $crate::Layer>::Output
+                >,
+            >
+        )
+    };
+
+    // Doesn't change the type of the wrapped layer
+    (@__field_config_ty_layer [[warn_if_default()] $([$($rest:tt)*])*] $($wrapped:tt)* ) => {
+        $crate::__private::make_config_impl!(@__field_config_ty_layer
+            [$([$($rest)*])*]
+            $($wrapped)*
+        )
+    };
+
     // ---------------
     // --------------- field config -> construction
     // ---------------
@@ -97,19 +197,88 @@ macro_rules! make_config_impl {
         <$Config as $crate::ConfigDescriptor>::define()
     }};
 
-    (@__field_config_def
-        var_name: $var_name:literal
-        $(, layers: [$($layers:tt)*])?
-        $(, description: $description:literal)?
-        $(, default_val_fmt: $default_val_fmt:literal)?
-        $(,)?
-    ) => {{
-        let __config = $crate::layers::TextVar::from_var_name($var_name)
-            $(.description($description))?
-            $(.default_fmt_val($default_val_fmt))?;
-        $crate::__private::make_config_impl!(@__field_config_def_layers __config $($($layers)*)?)
+    // The keys can be provided in any order, but the layers are applied after all the other keys
+    (@__field_config_def $($key:ident: $value:tt),+ $(,)?) => {{
+        let __config = $crate::layers::TextVar::from_var_name(
+            $crate::__private::make_config_impl!(@__field_var_name $($key: $value),+)
+        );
+        $(
+            let __config = $crate::__private::make_config_impl!(@__field_key_def __config $key: $value);
+        )+
+        $(
+            let __config = $crate::__private::make_config_impl!(@__field_layers_def __config $key: $value);
+        )+
+        __config
     }};
 
+    (@__field_var_name var_name: $var_name:literal $(, $($_rest:tt)*)?) => {
+        $var_name
+    };
+
+    (@__field_var_name $_key:ident: $_value:tt $(, $($rest:tt)*)?) => {
+        $crate::__private::make_config_impl!(@__field_var_name $($($rest)*)?)
+    };
+
+    (@__field_var_name) => {
+        $crate::__private::compile_error!("missing the `var_name` key")
+    };
+
+    // ---------------
+    // --------------- key -> method call
+    // ---------------
+
+    (@__field_key_def $binding:ident var_name: $_var_name:literal) => {
+        $binding
+    };
+
+    (@__field_key_def $binding:ident layers: [$($_layers:tt)*]) => {
+        $binding
+    };
+
+    (@__field_key_def $binding:ident description: $description:literal) => {
+        $binding.description($description)
+    };
+
+    (@__field_key_def $binding:ident default_val_fmt: $default_val_fmt:literal) => {
+        $binding.default_fmt_val($default_val_fmt)
+    };
+
+    (@__field_key_def $binding:ident deprecated_alias: $deprecated_alias:literal) => {
+        $binding.deprecated_alias($deprecated_alias)
+    };
+
+    (@__field_key_def $binding:ident example: $example:literal) => {
+        $binding.example($example)
+    };
+
+    (@__field_key_def $binding:ident required: $required:literal) => {
+        $binding.required($required)
+    };
+
+    (@__field_key_def $binding:ident secret: $secret:literal) => {
+        $binding.secret($secret)
+    };
+
+    (@__field_key_def $binding:ident type_name: $type_name:literal) => {
+        $binding.type_name($type_name)
+    };
+
+    (@__field_key_def $binding:ident tags: [$($tag:literal),* $(,)?]) => {
+        $binding.tags(&[$($tag),*])
+    };
+
+    (@__field_key_def $binding:ident deprecated: $note:literal) => {
+        $binding.deprecated($note)
+    };
+
+    (@__field_layers_def $binding:ident layers: [$($layers:tt)*]) => {
+        $crate::__private::make_config_impl!(@__field_config_def_layers $binding $($layers)*)
+    };
+
+    (@__field_layers_def $binding:ident $_key:ident: $_value:tt) => {
+        $binding
+    };
+
     (@__field_config_def_layers $binding:ident) => {
         $binding
     };
@@ -142,18 +311,326 @@ macro_rules! make_config_impl {
         $binding.parsed::<$parse_ty>($($parse_content)*)
     };
 
+    (@__field_config_def_layer $binding:ident parsed_with<$parse_ty:ty>($($parse_content:tt)*)) => {
+        $binding.parsed_with::<$parse_ty, $crate::layers::parsed::BoxedParseFn<$parse_ty>>(
+            $crate::__private::Box::new($($parse_content)*)
+        )
+    };
+
     (@__field_config_def_layer $binding:ident parsed_from_str<$parse_ty:ty>()) => {
         $binding.parsed_from_str::<$parse_ty>()
     };
 
-    (@__field_config_def_layer $binding:ident or_default_val($($or_default_val_content:tt)*)) => {
-        $binding.or_default_val($($or_default_val_content)*)
+    (@__field_config_def_layer $binding:ident byte_size()) => {
+        $binding.byte_size()
+    };
+
+    (@__field_config_def_layer $binding:ident one_of($($choices:tt)*)) => {
+        $binding.one_of($($choices)*)
+    };
+
+    (@__field_config_def_layer $binding:ident in_range($($range:tt)*)) => {
+        $binding.in_range($($range)*)
+    };
+
+    (@__field_config_def_layer $binding:ident flag_bool()) => {
+        $binding.flag_bool()
+    };
+
+    (@__field_config_def_layer $binding:ident flag_present()) => {
+        $binding.flag_present()
     };
 
-    (@__field_config_def_layer $binding:ident or_default()) => {
-        $binding.or_default()
+    (@__field_config_def_layer $binding:ident or_default_val($($or_default_val_content:tt)*)) => {{
+        let __layer = $binding.or_default_val($($or_default_val_content)*);
+        $crate::__private::make_config_impl!(@__default_fmt __layer)
+    }};
+
+    (@__field_config_def_layer $binding:ident or_default()) => {{
+        let __layer = $binding.or_default();
+        $crate::__private::make_config_impl!(@__default_fmt __layer)
+    }};
+
+    (@__field_config_def_layer $binding:ident or_default_with($($or_default_content:tt)*)) => {{
+        let __layer = $binding.or_default_with::<$crate::layers::or_default::BoxedDefaultFn<_>>(
+            $crate::__private::Box::new($($or_default_content)*)
+        );
+        $crate::__private::make_config_impl!(@__default_fmt __layer)
+    }};
+
+    (@__field_config_def_layer $binding:ident optional()) => {
+        $binding.optional()
     };
 
+    (@__field_config_def_layer $binding:ident or_default_val_strict($($or_default_val_content:tt)*)) => {{
+        let __layer = $binding.or_default_val_strict($($or_default_val_content)*);
+        $crate::__private::make_config_impl!(@__default_fmt __layer)
+    }};
+
+    (@__field_config_def_layer $binding:ident or_default_strict()) => {{
+        let __layer = $binding.or_default_strict();
+        $crate::__private::make_config_impl!(@__default_fmt __layer)
+    }};
+
+    (@__field_config_def_layer $binding:ident or_default_with_strict($($or_default_content:tt)*)) => {{
+        let __layer = $binding.or_default_with_strict::<$crate::layers::or_default::BoxedDefaultFn<_>>(
+            $crate::__private::Box::new($($or_default_content)*)
+        );
+        $crate::__private::make_config_impl!(@__default_fmt __layer)
+    }};
+
+    (@__field_config_def_layer $binding:ident warn_if_default()) => {
+        $binding.warn_if_default()
+    };
+
+    // Shows the default value in the descriptor, with its `Display` implementation, or its
+    // `Debug` implementation, or not at all. The default value isn't computed if the descriptor
+    // already shows one, or if the variable is secret.
+    (@__default_fmt $layer:ident) => {{
+        if $layer.__needs_default_fmt() {
+            #[allow(unused_imports)]
+            use $crate::__private::{FmtDefaultDebug as _, FmtDefaultDisplay as _, FmtDefaultNone as _};
+            let __default_val_fmt =
+                (&&&$crate::__private::DefaultValFmt(&$layer.__default_val())).__fmt_default();
+            $layer.__default_fmt(__default_val_fmt)
+        } else {
+            $layer
+        }
+    }};
+
+    // ---------------
+    // --------------- field config -> documentation
+    // ---------------
+
+    (@__field_doc $Config:ty) => {
+        $crate::__private::concat!(
+            "\n\nRead from the variables of the nested `",
+            $crate::__private::stringify!($Config),
+            "` configuration.",
+        )
+    };
+
+    (@__field_doc $($key:ident: $value:tt),+ $(,)?) => {
+        $crate::__private::make_config_impl!(@__field_doc_keys [] [] [] [] $($key: $value),+)
+    };
+
+    // Collects the variable name, the default value text, the secret flag and the layers, in this
+    // order
+    (@__field_doc_keys [$($_n:literal)?] [$($d:literal)?] [$($s:tt)?] [$($l:tt)*]
+        var_name: $var_name:literal $(, $($rest:tt)*)?
+    ) => {
+        $crate::__private::make_config_impl!(@__field_doc_keys [$var_name] [$($d)?] [$($s)?] [$($l)*]
+            $($($rest)*)?
+        )
+    };
+
+    (@__field_doc_keys [$($n:literal)?] [$($_d:literal)?] [$($s:tt)?] [$($l:tt)*]
+        default_val_fmt: $default_val_fmt:literal $(, $($rest:tt)*)?
+    ) => {
+        $crate::__private::make_config_impl!(@__field_doc_keys [$($n)?] [$default_val_fmt] [$($s)?] [$($l)*]
+            $($($rest)*)?
+        )
+    };
+
+    (@__field_doc_keys [$($n:literal)?] [$($d:literal)?] [$($_s:tt)?] [$($l:tt)*]
+        secret: $secret:tt $(, $($rest:tt)*)?
+    ) => {
+        $crate::__private::make_config_impl!(@__field_doc_keys [$($n)?] [$($d)?] [$secret] [$($l)*]
+            $($($rest)*)?
+        )
+    };
+
+    (@__field_doc_keys [$($n:literal)?] [$($d:literal)?] [$($s:tt)?] [$($_l:tt)*]
+        layers: [$($layers:tt)*] $(, $($rest:tt)*)?
+    ) => {
+        $crate::__private::make_config_impl!(@__field_doc_keys [$($n)?] [$($d)?] [$($s)?] [$($layers)*]
+            $($($rest)*)?
+        )
+    };
+
+    (@__field_doc_keys [$($n:literal)?] [$($d:literal)?] [$($s:tt)?] [$($l:tt)*]
+        $_key:ident: $_value:tt $(, $($rest:tt)*)?
+    ) => {
+        $crate::__private::make_config_impl!(@__field_doc_keys [$($n)?] [$($d)?] [$($s)?] [$($l)*]
+            $($($rest)*)?
+        )
+    };
+
+    // The default value of a secret variable is never shown
+    (@__field_doc_keys [$var_name:literal] [$($_d:literal)?] [true] [$($layers:tt)*]) => {
+        $crate::__private::make_config_impl!(@__field_doc_var $var_name [secret] $($layers)*)
+    };
+
+    (@__field_doc_keys [$var_name:literal] [$($d:literal)?] [$($_s:tt)?] [$($layers:tt)*]) => {
+        $crate::__private::make_config_impl!(@__field_doc_var $var_name [$($d)?] $($layers)*)
+    };
+
+    // The missing `var_name` key is reported by the definition
+    (@__field_doc_keys [] [$($_d:literal)?] [$($_s:tt)?] [$($_l:tt)*]) => {
+        ""
+    };
+
+    (@__field_doc_var $var_name:literal [$($d:tt)?] $($layers:tt)*) => {
+        $crate::__private::concat!(
+            "\n\nRead from the `", $var_name, "` environment variable: text",
+            $crate::__private::make_config_impl!(@__field_doc_layers [$($d)?] $($layers)*),
+            ".",
+        )
+    };
+
+    (@__field_doc_layers [$($_d:tt)?]) => {
+        ""
+    };
+
+    (@__field_doc_layers [$($d:tt)?]
+        $head:ident $(<$($head_gen_content:ty)*>)? ($($head_content:tt)*)
+        $(, $tail:ident $(<$($tail_gen_content:ty)*>)? ($($tail_content:tt)*))* $(,)?
+    ) => {
+        $crate::__private::concat!(
+            $crate::__private::make_config_impl!(@__field_doc_layer [$($d)?]
+                $head $(<$($head_gen_content)*>)? ($($head_content)*)
+            ),
+            $crate::__private::make_config_impl!(@__field_doc_layers [$($d)?]
+                $($tail $(<$($tail_gen_content)*>)? ($($tail_content)*)),*
+            ),
+        )
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] cached()) => {
+        " → cached"
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] file_read()) => {
+        " → file content"
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] $parsed:ident<$parse_ty:ty>($($_content:tt)*)) => {
+        $crate::__private::concat!(" → parse ", $crate::__private::stringify!($parse_ty))
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] byte_size()) => {
+        " → byte size"
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] one_of($($_content:tt)*)) => {
+        " → choice"
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] in_range($($_content:tt)*)) => {
+        " → range"
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] flag_bool()) => {
+        " → flag"
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] flag_present()) => {
+        " → presence flag"
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] optional()) => {
+        " → optional"
+    };
+
+    (@__field_doc_layer [$($d:tt)?] or_default_val_strict($($content:tt)*)) => {
+        $crate::__private::concat!(
+            " → default",
+            $crate::__private::make_config_impl!(@__field_doc_default [$($d)?] $($content)*),
+            " if unset",
+        )
+    };
+
+    (@__field_doc_layer [$($d:tt)?] or_default_strict()) => {
+        $crate::__private::concat!(
+            " → default",
+            $crate::__private::make_config_impl!(@__field_doc_default [$($d)?]),
+            " if unset",
+        )
+    };
+
+    (@__field_doc_layer [$($d:tt)?] or_default_with_strict($($content:tt)*)) => {
+        $crate::__private::concat!(
+            " → default",
+            $crate::__private::make_config_impl!(@__field_doc_default [$($d)?] $($content)*),
+            " if unset",
+        )
+    };
+
+    (@__field_doc_layer [$($_d:tt)?] warn_if_default()) => {
+        ""
+    };
+
+    // `or_default`, `or_default_val` and `or_default_with`
+    (@__field_doc_layer [$($d:tt)?] $or_default:ident($($content:tt)*)) => {
+        $crate::__private::concat!(
+            " → default",
+            $crate::__private::make_config_impl!(@__field_doc_default [$($d)?] $($content)*),
+        )
+    };
+
+    // The text of the default value, as shown by the reports when it is known at compile time:
+    // the `default_val_fmt` key, or the literal returned by the default value function
+    (@__field_doc_default [secret] $($_content:tt)*) => {
+        ""
+    };
+
+    (@__field_doc_default [$default_val_fmt:literal] $($_content:tt)*) => {
+        $crate::__private::concat!(" ", $default_val_fmt)
+    };
+
+    (@__field_doc_default [] || $default_val:literal) => {
+        $crate::__private::concat!(" ", $default_val)
+    };
+
+    (@__field_doc_default [] $($_content:tt)*) => {
+        ""
+    };
+
+    // ---------------
+    // --------------- field kind -> variable names
+    // ---------------
+
+    (@__field_var_names $Config:ty) => {
+        $crate::__private::VarNames::Nested(<$Config as $crate::ConfigDescriptor>::__VAR_NAMES)
+    };
+
+    (@__field_var_names $($key:ident: $value:tt),+ $(,)?) => {
+        $crate::__private::VarNames::Var {
+            name: $crate::__private::make_config_impl!(@__field_var_name $($key: $value),+),
+            alias: $crate::__private::make_config_impl!(@__field_deprecated_alias $($key: $value),+),
+        }
+    };
+
+    (@__field_deprecated_alias deprecated_alias: $alias:literal $(, $($_rest:tt)*)?) => {
+        $crate::__private::Option::Some($alias)
+    };
+
+    (@__field_deprecated_alias $_key:ident: $_value:tt $(, $($rest:tt)*)?) => {
+        $crate::__private::make_config_impl!(@__field_deprecated_alias $($($rest)*)?)
+    };
+
+    (@__field_deprecated_alias) => {
+        $crate::__private::Option::None
+    };
+
+    // ---------------
+    // --------------- field kind -> visit
+    // ---------------
+
+    (@__field_visit $self:ident $visitor:ident $field:ident $Config:ty) => {{
+        $visitor.enter_group(stringify!($field));
+        <$Config as $crate::ConfigDescriptor>::visit(&$self.$field, $visitor);
+        $visitor.exit_group(stringify!($field));
+    }};
+
+    (@__field_visit $self:ident $visitor:ident $field:ident $($_field_config:tt)*) => {{
+        $visitor.visit_var(
+            stringify!($field),
+            $crate::ConfigValueDescriptor::get_descriptor(&$self.$field),
+            &$crate::DescribeLayer::describe(&$self.$field),
+        );
+    }};
+
     // ---------------
     // --------------- field kinds -> iter type
     // ---------------
@@ -175,12 +652,14 @@ macro_rules! make_config_impl {
     // ---------------
 
     (@__field_kind_ty $lt:lifetime $Config:ty) => {
-        <<$Config as $crate::exec::ConfigInitializer>::Iter<$lt> as
-            $crate::__private::iter::IntoIterator>::IntoIter
+        $crate::exec::Prefixed<
+            <<$Config as $crate::exec::ConfigInitializer>::Iter<$lt> as
+                $crate::__private::iter::IntoIterator>::IntoIter
+        >
     };
 
-    (@__field_kind_ty $lt:lifetime var_name $($_rest:tt)*) => {
-        $crate::__private::iter::Once<$crate::exec::ExecResult<$lt>>
+    (@__field_kind_ty $lt:lifetime $($_field_config:tt)*) => {
+        $crate::exec::Prefixed<$crate::__private::iter::Once<$crate::exec::ExecResult<$lt>>>
     };
 
     // ---------------
@@ -201,16 +680,45 @@ macro_rules! make_config_impl {
     // ---------------
 
     (@__field_kind_call $self:ident $field:ident $Config:ty) => {
-        <$Config as $crate::exec::ConfigInitializer>::init_raw(&$self.$field)
-            .into_iter()
+        $crate::exec::Prefixed::new(
+            stringify!($field),
+            <$Config as $crate::exec::ConfigInitializer>::init_raw(&$self.$field).into_iter(),
+        )
     };
 
-    (@__field_kind_call $self:ident $field:ident var_name $($_rest:tt)*) => {
-        $crate::__private::iter::once(
-            $crate::exec::ExecResult {
-                config: $self.$field.get_descriptor(),
-                error: $self.$field.try_get().err().map(From::from),
-            }
+    (@__field_kind_call $self:ident $field:ident $($_field_config:tt)*) => {
+        $crate::exec::Prefixed::new(
+            stringify!($field),
+            $crate::__private::iter::once($crate::exec::ExecResult::from_sync_config(&$self.$field)),
+        )
+    };
+
+    // ---------------
+    // --------------- field kind -> lazy iter calls
+    // ---------------
+
+    (@__field_lazy_calls $self:ident [$head_field:ident: $($head_config:tt)*] $([$($tail:tt)*])*) => {
+        $crate::__private::make_config_impl!(@__field_lazy_call $self $head_field $($head_config)*)
+            .chain($crate::__private::make_config_impl!(@__field_lazy_calls $self $([$($tail)*])*))
+    };
+
+    (@__field_lazy_calls $_self:ident) => {
+        $crate::__private::iter::empty::<$crate::exec::ExecResult<'_>>()
+    };
+
+    (@__field_lazy_call $self:ident $field:ident $Config:ty) => {
+        $crate::exec::Prefixed::new(
+            stringify!($field),
+            <$Config as $crate::exec::ConfigInitializer>::init_lazy(&$self.$field),
+        )
+    };
+
+    // The value is only read when the iterator reaches it
+    (@__field_lazy_call $self:ident $field:ident $($_field_config:tt)*) => {
+        $crate::exec::Prefixed::new(
+            stringify!($field),
+            $crate::__private::iter::once(&$self.$field)
+                .map($crate::exec::ExecResult::from_sync_config),
         )
     };
 }
@@ -231,12 +739,17 @@ macro_rules! make_config {
         $(#[$main_attr])*
         $vis struct $Name {$(
             $(#[$field_attr])*
+            #[doc = $crate::__private::make_config_impl!(@__field_doc $($field_config)*)]
             $field_vis $field: $crate::__private::make_config_impl!(@__field_config_ty $($field_config)*),
         )*}
 
         const _: () = {
             #[automatically_derived]
             impl $crate::ConfigDescriptor for $Name {
+                const __VAR_NAMES: &'static [$crate::__private::VarNames] = &[$(
+                    $crate::__private::make_config_impl!(@__field_var_names $($field_config)*)
+                ),*];
+
                 fn define() -> Self {
                     #[allow(unused_imports)]
                     use $crate::prelude::*;
@@ -244,8 +757,21 @@ macro_rules! make_config {
                         $field: $crate::__private::make_config_impl!(@__field_config_def $($field_config)*)
                     ),*}
                 }
+
+                fn visit<'__a>(&'__a self, __visitor: &mut dyn $crate::ConfigVisitor<'__a>) {
+                    $(
+                        $crate::__private::make_config_impl!(
+                            @__field_visit self __visitor $field $($field_config)*
+                        );
+                    )*
+                }
             }
 
+            // Rejects the duplicate variable names, including the ones of nested configurations
+            const _: () = $crate::__private::check_var_names(
+                <$Name as $crate::ConfigDescriptor>::__VAR_NAMES
+            );
+
             #[automatically_derived]
             impl $crate::exec::ConfigInitializer for $Name {
                 type Iter<'a> = $crate::__private::make_config_impl!(@__field_kind
@@ -260,6 +786,15 @@ macro_rules! make_config {
                         @__field_kind_calls self $([ $field: $($field_config)* ])*
                     )
                 }
+
+                fn init_lazy(&self) -> $crate::exec::LazyResults<'_> {
+                    #[allow(unused_imports)]
+                    use $crate::prelude::*;
+
+                    $crate::__private::Box::new($crate::__private::make_config_impl!(
+                        @__field_lazy_calls self $([ $field: $($field_config)* ])*
+                    ))
+                }
             }
         };
     }
@@ -269,6 +804,33 @@ macro_rules! make_config {
 mod tests {
     use crate::prelude::*;
 
+    #[test]
+    fn assert_field_doc() {
+        assert_eq!(
+            make_config_impl!(@__field_doc
+                var_name: "PORT",
+                layers: [parsed_from_str<u16>(), or_default_val(|| 8080)],
+            ),
+            "\n\nRead from the `PORT` environment variable: text → parse u16 → default 8080.",
+        );
+        assert_eq!(
+            make_config_impl!(@__field_doc
+                var_name: "HOST",
+                default_val_fmt: "the local host",
+                layers: [or_default_val_strict(|| "localhost".to_owned())],
+            ),
+            "\n\nRead from the `HOST` environment variable: text → default the local host if unset.",
+        );
+        assert_eq!(
+            make_config_impl!(@__field_doc
+                var_name: "TOKEN",
+                layers: [or_default_val(|| "hunter2")],
+                secret: true,
+            ),
+            "\n\nRead from the `TOKEN` environment variable: text → default.",
+        );
+    }
+
     #[test]
     fn assert_result_iter_coherent_no_flattening() {
         make_config! {
@@ -295,11 +857,55 @@ mod tests {
         let res = config.init_raw();
 
         itertools::assert_equal(
-            res.map(|res| res.config.var_name),
+            res.map(|res| res.descriptor().var_name),
             ["VAR_A", "VAR_B", "VAR_C"],
         );
     }
 
+    #[test]
+    fn assert_metadata_keys() {
+        make_config! {
+            struct TestConfig {
+                token: {
+                    secret: true,
+                    description: "The API token",
+                    tags: ["auth"],
+                    var_name: "__TEST_METADATA_TOKEN",
+                    example: "abc123",
+                },
+                port: {
+                    layers: [parsed_from_str<u16>(), or_default_val(|| 80)],
+                    var_name: "__TEST_METADATA_PORT",
+                    required: false,
+                    default_val_fmt: "80",
+                    type_name: "port",
+                    deprecated: "the port is now chosen by the system",
+                },
+            }
+        }
+
+        let config = TestConfig::define();
+
+        let token = config.token.get_descriptor();
+        assert_eq!(token.var_name, "__TEST_METADATA_TOKEN");
+        assert_eq!(token.description(), Some("The API token"));
+        assert_eq!(token.example(), Some("abc123"));
+        assert_eq!(token.tags(), ["auth"]);
+        assert!(token.is_required());
+        assert!(token.is_secret());
+        assert_eq!(token.deprecated(), None);
+
+        let port = config.port.get_descriptor();
+        assert_eq!(port.default_val_fmt(), Some("80"));
+        assert_eq!(port.type_name(), Some("port"));
+        assert!(!port.is_required());
+        assert!(!port.is_secret());
+        assert_eq!(
+            port.to_string(),
+            "`__TEST_METADATA_PORT` (default: 80) (deprecated: the port is now chosen by the system)"
+        );
+    }
+
     #[test]
     fn assert_result_iter_coherent_with_flattening() {
         make_config! {
@@ -329,11 +935,48 @@ mod tests {
         let res = config.init_raw();
 
         itertools::assert_equal(
-            res.map(|res| res.config.var_name),
+            res.map(|res| res.descriptor().var_name),
             ["VAR_A", "VAR_B", "VAR_C", "VAR_D"],
         );
     }
 
+    #[test]
+    fn assert_result_paths() {
+        make_config! {
+            struct Foo {
+                var_a: {
+                    var_name: "VAR_A",
+                },
+            }
+        }
+
+        make_config! {
+            struct Bar {
+                foo: { Foo },
+                var_b: {
+                    var_name: "VAR_B",
+                },
+            }
+        }
+
+        make_config! {
+            struct Baz {
+                bar: { Bar },
+                var_c: {
+                    var_name: "VAR_C",
+                },
+            }
+        }
+
+        let config = Baz::define();
+        let res = config.init_raw();
+
+        itertools::assert_equal(
+            res.map(|res| res.path().join(".")),
+            ["bar.foo.var_a", "bar.var_b", "var_c"],
+        );
+    }
+
     /// Contains declarations made with the macro, to make sure the code still compiles
     /// with some tweaks.
     #[cfg(debug_assertions)]
@@ -424,7 +1067,7 @@ mod tests {
                 bar: {
                     var_name: "HEY",
                 },
-                foobar: { Foo8 },
+                foobar: { Foo1 },
             }
         }
 
@@ -480,5 +1123,100 @@ mod tests {
                 }
             }
         }
+
+        // byte size layer
+        make_config! {
+            struct Foo15 {
+                foo: {
+                    var_name: "HEY",
+                    layers: [
+                        file_read(),
+                        byte_size(),
+                        or_default_val(|| 1 << 20),
+                    ],
+                }
+            }
+        }
+
+        // capturing closures
+        make_config! {
+            struct Foo17 {
+                foo: {
+                    var_name: "HEY",
+                    layers: [
+                        parsed_with<std::path::PathBuf>({
+                            let base = std::path::PathBuf::from("/srv");
+                            move |input| Ok(base.join(input))
+                        }),
+                        or_default_with({
+                            let default = std::path::PathBuf::from("/tmp");
+                            move || default.clone()
+                        }),
+                    ],
+                },
+                bar: {
+                    var_name: "HEY_STRICT",
+                    layers: [
+                        parsed_from_str<u16>(),
+                        or_default_with_strict({
+                            let default = 8000 + 80;
+                            move || default
+                        }),
+                    ],
+                },
+            }
+        }
+
+        // warnings
+        make_config! {
+            struct Foo18 {
+                foo: {
+                    var_name: "HEY",
+                    layers: [parsed_from_str<u16>(), or_default_val(|| 80), warn_if_default()],
+                    description: "hey",
+                    default_val_fmt: "80",
+                    deprecated_alias: "OLD_HEY",
+                },
+                bar: {
+                    var_name: "HO",
+                    deprecated_alias: "OLD_HO",
+                },
+            }
+        }
+
+        // metadata keys, in any order
+        make_config! {
+            struct Foo19 {
+                foo: {
+                    tags: ["net", "http"],
+                    layers: [parsed_from_str<u16>(), or_default_val(|| 80)],
+                    secret: false,
+                    var_name: "HEY",
+                    required: false,
+                    example: "8080",
+                    type_name: "port",
+                    deprecated: "use HO instead",
+                },
+                bar: {
+                    tags: [],
+                    secret: true,
+                    var_name: "HO",
+                },
+            }
+        }
+
+        // boolean flag layers
+        make_config! {
+            struct Foo16 {
+                foo: {
+                    var_name: "HEY",
+                    layers: [flag_bool(), or_default()],
+                },
+                bar: {
+                    var_name: "HO",
+                    layers: [flag_present()],
+                },
+            }
+        }
     }
 }
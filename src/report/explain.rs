@@ -0,0 +1,217 @@
+//! Module containing everything related to the [`Explanation`] of a configuration read.
+
+use std::{error::Error, fmt, iter};
+
+use crate::{
+    descriptor::{ConfigValueDescriptor, DescribeLayer},
+    error::LayerError,
+    exec::ExecResult,
+    layer::{Layer, ReadStep},
+    report::{InitReport, ReportEntry},
+};
+
+/// The explanation of how each variable of a configuration was resolved.
+///
+/// Its [`Display`](fmt::Display) implementation prints the resolution chain of every variable:
+/// the environment variables and files that were read, the default values that were used and why,
+/// and the source the value was finally resolved from. The values themselves are never printed.
+///
+/// To get it, see [`ConfigInitializer::explain`][1] and [`LayerExt::explain`][2].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::prelude::*;
+/// let port = TextVar::from_var_name("EXPLAIN_PORT")
+///   .parsed_from_str::<u16>()
+///   .or_default_val(|| 8080);
+///
+/// assert_eq!(
+///   port.explain().to_string(),
+///   "`EXPLAIN_PORT`\n\
+///   \x20 1. the environment variable `EXPLAIN_PORT` isn't set\n\
+///   \x20 2. used the default value, because the environment variable isn't set\n\
+///   \x20 => resolved from the default value\n"
+/// );
+/// ```
+///
+/// [1]: crate::exec::ConfigInitializer::explain
+/// [2]: crate::builder::LayerExt::explain
+#[derive(Debug)]
+pub struct Explanation<'a> {
+    pub(crate) report: InitReport<'a>,
+}
+
+impl<'a> Explanation<'a> {
+    /// Reads the configuration value, and explains its resolution.
+    pub(crate) fn from_config<T>(config: &'a T) -> Self
+    where
+        &'a T: Layer<Error: LayerError>,
+        Box<dyn Error + Send + Sync + 'a>: From<<&'a T as Layer>::Error>,
+        T: ConfigValueDescriptor + DescribeLayer,
+    {
+        Self {
+            report: InitReport::new(iter::once(ExecResult::from_config(config))),
+        }
+    }
+
+    /// Returns the report of the read the explanation is made from.
+    #[inline]
+    pub fn report(&self) -> &InitReport<'a> {
+        &self.report
+    }
+
+    /// Converts the explanation into the report of the read.
+    #[inline]
+    pub fn into_report(self) -> InitReport<'a> {
+        self.report
+    }
+}
+
+fn fmt_entry(entry: &ReportEntry<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "`{}`", entry.descriptor().var_name)?;
+    if !entry.path().is_empty() {
+        write!(f, " ({})", entry.path().join("."))?;
+    }
+    writeln!(f)?;
+
+    for (i, step) in entry.trace().steps().iter().enumerate() {
+        writeln!(f, "  {}. {step}", i + 1)?;
+    }
+
+    match entry.error() {
+        Some(err) => writeln!(f, "  => failed: {err}"),
+        None => match entry.trace().source() {
+            Some(ReadStep::Var { var_name, .. }) => {
+                writeln!(
+                    f,
+                    "  => resolved from the environment variable `{var_name}`"
+                )
+            }
+            Some(ReadStep::File { path }) => {
+                writeln!(f, "  => resolved from the file `{}`", path.display())
+            }
+            Some(ReadStep::Default { .. }) => writeln!(f, "  => resolved from the default value"),
+            None => writeln!(f, "  => resolved without any recorded source"),
+        },
+    }
+}
+
+impl fmt::Display for Explanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.report.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            fmt_entry(entry, f)?;
+        }
+        if self.report.unchecked_count() > 0 {
+            writeln!(
+                f,
+                "\n{} variable(s) left unchecked",
+                self.report.unchecked_count()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        DefaultCause, ReadStep,
+        error::ErrorKind,
+        prelude::*,
+        tests::{assert_matches, with_env},
+    };
+
+    crate::make_config! {
+        struct TestConfig {
+            token: {
+                var_name: "__TEST_EXPLAIN_TOKEN",
+                layers: [file_read()],
+            },
+            port: {
+                var_name: "__TEST_EXPLAIN_PORT",
+                layers: [parsed_from_str<u16>(), or_default_val(|| 8080)],
+            },
+            host: {
+                var_name: "__TEST_EXPLAIN_HOST",
+                deprecated_alias: "__TEST_EXPLAIN_OLD_HOST",
+            },
+        }
+    }
+
+    #[test]
+    fn assert_provenance() {
+        const FILE_PATH: &str = "./__test_explain_token";
+        fs::write(FILE_PATH, "secret").unwrap();
+
+        let config = TestConfig::define();
+        let report = with_env(
+            [
+                ("__TEST_EXPLAIN_TOKEN", FILE_PATH),
+                ("__TEST_EXPLAIN_PORT", "80a"),
+                ("__TEST_EXPLAIN_OLD_HOST", "localhost"),
+            ],
+            || config.init_report(),
+        );
+        fs::remove_file(FILE_PATH).unwrap();
+
+        let mut entries = report.iter();
+        let token = entries.next().unwrap().trace();
+        assert_matches!(
+            token.source(),
+            Some(ReadStep::File { path }) if path.ends_with("__test_explain_token")
+        );
+        let port = entries.next().unwrap().trace();
+        assert_eq!(
+            port.default_cause(),
+            Some(DefaultCause::ReadFailed {
+                kind: ErrorKind::Parse
+            })
+        );
+        assert_eq!(
+            port.steps().last().unwrap().to_string(),
+            "used the default value, because the value couldn't be parsed"
+        );
+        let host = entries.next().unwrap().trace();
+        itertools::assert_equal(
+            host.steps(),
+            &[
+                ReadStep::Var {
+                    var_name: "__TEST_EXPLAIN_HOST",
+                    present: false,
+                },
+                ReadStep::Var {
+                    var_name: "__TEST_EXPLAIN_OLD_HOST",
+                    present: true,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn assert_explain() {
+        let config = TestConfig::define();
+        let explanation = with_env([("__TEST_EXPLAIN_HOST", "localhost")], || config.explain());
+
+        assert_eq!(
+            explanation.to_string(),
+            "`__TEST_EXPLAIN_TOKEN` (token)\n\
+             \x20 1. the environment variable `__TEST_EXPLAIN_TOKEN` isn't set\n\
+             \x20 => failed: environment variable not found\n\
+             \n\
+             `__TEST_EXPLAIN_PORT` (port)\n\
+             \x20 1. the environment variable `__TEST_EXPLAIN_PORT` isn't set\n\
+             \x20 2. used the default value, because the environment variable isn't set\n\
+             \x20 => resolved from the default value\n\
+             \n\
+             `__TEST_EXPLAIN_HOST` (host)\n\
+             \x20 1. read the environment variable `__TEST_EXPLAIN_HOST`\n\
+             \x20 => resolved from the environment variable `__TEST_EXPLAIN_HOST`\n"
+        );
+    }
+}
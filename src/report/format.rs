@@ -0,0 +1,913 @@
+//! Module containing everything related to the formatting of the reports.
+
+use std::{
+    fmt,
+    io::{self, IsTerminal as _},
+    sync::{Arc, RwLock},
+};
+
+use crate::{
+    descriptor::VarDescriptor,
+    json,
+    layer::ReadWarning,
+    report::{InitReport, InitWarnings, ReportEntry},
+};
+
+/// Represents types able to format the [report][1] of a configuration read.
+///
+/// The formatter used by default is [`Plain`]. It can be changed globally with
+/// [`set_formatter`], which is used by the [`Display`](fmt::Display) implementation of the
+/// initialization errors, and by the panic message of [`Layer::get`][2].
+///
+/// A specific formatter may also be used for a single report, with
+/// [`InitReport::display_with`] or [`ConfigInitializer::init_with`][3].
+///
+/// [1]: InitReport
+/// [2]: crate::Layer::get
+/// [3]: crate::exec::ConfigInitializer::init_with
+pub trait ReportFormatter: Send + Sync {
+    /// Writes the report of a whole configuration read.
+    fn fmt_report(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// Writes the report of a configuration read that failed.
+    ///
+    /// The default implementation writes a header line, followed by the report.
+    fn fmt_init_error(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Error during configuration initialization:")?;
+        self.fmt_report(report, f)
+    }
+
+    /// Writes the warnings of a successful configuration read.
+    ///
+    /// This is used by [`ConfigInitializer::init`][1], that prints the warnings to the standard
+    /// error.
+    ///
+    /// [1]: crate::exec::ConfigInitializer::init
+    fn fmt_warnings(&self, warnings: &InitWarnings, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_warning_lines(var_warnings(warnings), f, &Style::PLAIN)
+    }
+
+    /// Writes the error of the read of a single configuration value.
+    ///
+    /// This is used for the panic message of [`Layer::get`](crate::Layer::get). `type_name` is
+    /// the name of the output type of the configuration value.
+    fn fmt_var_error(
+        &self,
+        descriptor: &VarDescriptor,
+        type_name: &str,
+        error: &dyn fmt::Display,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "couldn't get env var `{}` (expected type `{type_name}`): {error}",
+            descriptor.var_name,
+        )
+    }
+}
+
+impl<T: ReportFormatter + ?Sized> ReportFormatter for &T {
+    #[inline]
+    fn fmt_report(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt_report(self, report, f)
+    }
+
+    #[inline]
+    fn fmt_init_error(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt_init_error(self, report, f)
+    }
+
+    #[inline]
+    fn fmt_warnings(&self, warnings: &InitWarnings, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt_warnings(self, warnings, f)
+    }
+
+    #[inline]
+    fn fmt_var_error(
+        &self,
+        descriptor: &VarDescriptor,
+        type_name: &str,
+        error: &dyn fmt::Display,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        T::fmt_var_error(self, descriptor, type_name, error, f)
+    }
+}
+
+/// The plain formatter, used by default.
+///
+/// It lists the incorrect variables with their error, then the valid ones, then the description
+/// of the full environment:
+///
+/// ```txt
+/// Got 1 incorrect variable
+/// - `PWD`: environment variable not found
+/// Got 1 valid variable
+/// - `USER`
+/// Note: full required environment description:
+/// - `PWD`
+/// - `USER`
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Plain;
+
+impl ReportFormatter for Plain {
+    fn fmt_report(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_sections(report, f, &Style::PLAIN)
+    }
+}
+
+/// The same formatter as [`Plain`], with ANSI colors.
+///
+/// See [`Auto`] to only use colors when the standard error is a terminal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ansi;
+
+impl ReportFormatter for Ansi {
+    fn fmt_report(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_sections(report, f, &Style::ANSI)
+    }
+
+    fn fmt_init_error(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}Error during configuration initialization:{}",
+            Style::ANSI.error,
+            Style::ANSI.reset
+        )?;
+        self.fmt_report(report, f)
+    }
+
+    fn fmt_warnings(&self, warnings: &InitWarnings, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_warning_lines(var_warnings(warnings), f, &Style::ANSI)
+    }
+
+    fn fmt_var_error(
+        &self,
+        descriptor: &VarDescriptor,
+        type_name: &str,
+        error: &dyn fmt::Display,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        let Style {
+            error: red,
+            name,
+            reset,
+            ..
+        } = Style::ANSI;
+        write!(
+            f,
+            "{red}couldn't get env var{reset} {name}`{}`{reset} (expected type `{type_name}`): \
+             {error}",
+            descriptor.var_name,
+        )
+    }
+}
+
+/// Uses the [`Ansi`] formatter if the standard error is a terminal, and the [`Plain`] formatter
+/// otherwise.
+///
+/// Following the [`NO_COLOR`](https://no-color.org) convention, colors are also disabled if the
+/// `NO_COLOR` environment variable is set to a non-empty value.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Auto;
+
+impl Auto {
+    fn colored() -> bool {
+        std::env::var_os("NO_COLOR").is_none_or(|val| val.is_empty()) && io::stderr().is_terminal()
+    }
+}
+
+impl ReportFormatter for Auto {
+    fn fmt_report(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if Self::colored() {
+            Ansi.fmt_report(report, f)
+        } else {
+            Plain.fmt_report(report, f)
+        }
+    }
+
+    fn fmt_init_error(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if Self::colored() {
+            Ansi.fmt_init_error(report, f)
+        } else {
+            Plain.fmt_init_error(report, f)
+        }
+    }
+
+    fn fmt_warnings(&self, warnings: &InitWarnings, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if Self::colored() {
+            Ansi.fmt_warnings(warnings, f)
+        } else {
+            Plain.fmt_warnings(warnings, f)
+        }
+    }
+
+    fn fmt_var_error(
+        &self,
+        descriptor: &VarDescriptor,
+        type_name: &str,
+        error: &dyn fmt::Display,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        if Self::colored() {
+            Ansi.fmt_var_error(descriptor, type_name, error, f)
+        } else {
+            Plain.fmt_var_error(descriptor, type_name, error, f)
+        }
+    }
+}
+
+/// A compact formatter, writing one line per incorrect variable.
+///
+/// It is meant for log aggregators. Each line is in the `logfmt` format, e.g.:
+///
+/// ```txt
+/// level=error var=PORT kind=parse error="parse error: invalid digit found in string"
+/// ```
+///
+/// If the read stopped at the first error, a last line tells how many variables were left
+/// unchecked. Nothing is written if every variable was correctly read.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Compact;
+
+impl ReportFormatter for Compact {
+    fn fmt_report(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in report.invalid() {
+            write!(f, "level=error var={}", entry.descriptor().var_name)?;
+            if let Some(kind) = entry.error_kind() {
+                write!(f, " kind={kind}")?;
+            }
+            if let Some(err) = entry.error() {
+                f.write_str(" error=")?;
+                json::write_str(f, &err.to_string())?;
+            }
+            if !entry.similar_vars().is_empty() {
+                f.write_str(" similar=")?;
+                json::write_str(f, &entry.similar_vars().join(","))?;
+            }
+            writeln!(f)?;
+        }
+        if report.unchecked_count() > 0 {
+            writeln!(f, "level=warn unchecked={}", report.unchecked_count())?;
+        }
+        for (entry, warning) in report.warnings() {
+            fmt_compact_warning(entry.descriptor(), warning, f)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn fmt_init_error(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_report(report, f)
+    }
+
+    fn fmt_warnings(&self, warnings: &InitWarnings, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for warning in warnings {
+            fmt_compact_warning(warning.descriptor(), warning.warning(), f)?;
+        }
+        Ok(())
+    }
+
+    fn fmt_var_error(
+        &self,
+        descriptor: &VarDescriptor,
+        type_name: &str,
+        error: &dyn fmt::Display,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "level=error var={} type={type_name} error=",
+            descriptor.var_name
+        )?;
+        json::write_str(f, &error.to_string())
+    }
+}
+
+/// A formatter grouping the variables by nested configuration, in a tree.
+///
+/// Each variable is shown with the name of its field, and the groups are the fields holding
+/// nested configurations. The groups containing incorrect variables are marked, which makes it
+/// obvious which component is misconfigured:
+///
+/// ```txt
+/// Got 1 incorrect variable, 2 valid variables
+/// ├── db (1 incorrect)
+/// │   └── db_url `DB_URL`: environment variable not found
+/// ├── user `USER`: ok
+/// └── request_timeout `REQUEST_TIMEOUT`: ok (default used)
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tree;
+
+impl ReportFormatter for Tree {
+    fn fmt_report(&self, report: &InitReport<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (incorrect_count, correct_count) = (report.invalid_count(), report.valid_count());
+        writeln!(
+            f,
+            "Got {} incorrect variable{}, {} valid variable{}",
+            incorrect_count,
+            if incorrect_count > 1 { "s" } else { "" },
+            correct_count,
+            if correct_count > 1 { "s" } else { "" },
+        )?;
+
+        let entries = report.iter().collect::<Vec<_>>();
+        fmt_tree_level(&entries, 0, &mut String::new(), f)?;
+
+        let unchecked_count = report.unchecked_count();
+        if unchecked_count > 0 {
+            writeln!(
+                f,
+                "Stopped at the first error, {} variable{} left unchecked",
+                unchecked_count,
+                if unchecked_count > 1 { "s" } else { "" }
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+enum TreeNode<'r, 'a> {
+    Leaf(&'r ReportEntry<'a>),
+    Group(&'static str, &'r [&'r ReportEntry<'a>]),
+}
+
+/// Writes the entries sharing the same path up to `depth`.
+///
+/// The entries are in declaration order, so the entries of the same group are contiguous.
+fn fmt_tree_level(
+    entries: &[&ReportEntry<'_>],
+    depth: usize,
+    prefix: &mut String,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let mut nodes = Vec::new();
+    let mut i = 0;
+    while i < entries.len() {
+        let path = entries[i].path();
+        if path.len() > depth + 1 {
+            let segment = path[depth];
+            let len = entries[i..]
+                .iter()
+                .take_while(|entry| {
+                    entry.path().len() > depth + 1 && entry.path()[depth] == segment
+                })
+                .count();
+            nodes.push(TreeNode::Group(segment, &entries[i..i + len]));
+            i += len;
+        } else {
+            nodes.push(TreeNode::Leaf(entries[i]));
+            i += 1;
+        }
+    }
+
+    let count = nodes.len();
+    for (i, node) in nodes.into_iter().enumerate() {
+        let is_last = i + 1 == count;
+        write!(f, "{prefix}{}", if is_last { "└── " } else { "├── " })?;
+
+        match node {
+            TreeNode::Leaf(entry) => {
+                if let Some(field) = entry.path().last() {
+                    write!(f, "{field} ")?;
+                }
+                write!(f, "`{}`: ", entry.descriptor().var_name)?;
+                match entry.error() {
+                    Some(err) => write!(f, "{err}")?,
+                    None => f.write_str("ok")?,
+                }
+                fmt_similar_vars(entry, f)?;
+                if entry.used_default() {
+                    f.write_str(" (default used)")?;
+                }
+                for warning in entry.warnings() {
+                    write!(f, ", warning: {warning}")?;
+                }
+                writeln!(f)?;
+            }
+            TreeNode::Group(segment, group) => {
+                f.write_str(segment)?;
+                let incorrect_count = group.iter().filter(|entry| !entry.is_valid()).count();
+                if incorrect_count > 0 {
+                    write!(f, " ({incorrect_count} incorrect)")?;
+                }
+                writeln!(f)?;
+
+                let prefix_len = prefix.len();
+                prefix.push_str(if is_last { "    " } else { "│   " });
+                fmt_tree_level(group, depth + 1, prefix, f)?;
+                prefix.truncate(prefix_len);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the hint about the environment variables similar to a missing one, if there is any.
+fn fmt_similar_vars(entry: &ReportEntry<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let Some((last, others)) = entry.similar_vars().split_last() else {
+        return Ok(());
+    };
+
+    f.write_str(" (did you mean ")?;
+    for (i, var_name) in others.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "`{var_name}`")?;
+    }
+    if !others.is_empty() {
+        f.write_str(" or ")?;
+    }
+    write!(f, "`{last}`?)")
+}
+
+fn fmt_compact_warning(
+    descriptor: &VarDescriptor,
+    warning: &ReadWarning,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    write!(
+        f,
+        "level=warn var={} kind={} warning=",
+        descriptor.var_name,
+        warning.as_str()
+    )?;
+    json::write_str(f, &warning.to_string())?;
+    writeln!(f)
+}
+
+struct Style {
+    error: &'static str,
+    warning: &'static str,
+    valid: &'static str,
+    name: &'static str,
+    note: &'static str,
+    reset: &'static str,
+}
+
+impl Style {
+    const PLAIN: Self = Self {
+        error: "",
+        warning: "",
+        valid: "",
+        name: "",
+        note: "",
+        reset: "",
+    };
+
+    const ANSI: Self = Self {
+        error: "\x1b[1;31m",
+        warning: "\x1b[1;33m",
+        valid: "\x1b[1;32m",
+        name: "\x1b[1m",
+        note: "\x1b[2m",
+        reset: "\x1b[0m",
+    };
+}
+
+fn fmt_sections(report: &InitReport<'_>, f: &mut fmt::Formatter<'_>, style: &Style) -> fmt::Result {
+    let Style {
+        error,
+        valid,
+        name,
+        note,
+        reset,
+        ..
+    } = style;
+
+    let incorrect_count = report.invalid_count();
+    writeln!(
+        f,
+        "{error}Got {} incorrect variable{}{reset}",
+        incorrect_count,
+        if incorrect_count > 1 { "s" } else { "" }
+    )?;
+
+    for entry in report.invalid() {
+        if let Some(err) = entry.error() {
+            write!(f, "- {name}`{}`{reset}: {err}", entry.descriptor().var_name)?;
+            fmt_similar_vars(entry, f)?;
+            writeln!(f)?;
+        }
+    }
+
+    let unchecked_count = report.unchecked_count();
+    if unchecked_count > 0 {
+        writeln!(
+            f,
+            "{note}Stopped at the first error, {} variable{} left unchecked{reset}",
+            unchecked_count,
+            if unchecked_count > 1 { "s" } else { "" }
+        )?;
+    }
+
+    let correct_count = report.valid_count();
+    writeln!(
+        f,
+        "{valid}Got {} valid variable{}{reset}",
+        correct_count,
+        if correct_count > 1 { "s" } else { "" }
+    )?;
+
+    for entry in report.valid() {
+        writeln!(f, "- {name}`{}`{reset}", entry.descriptor().var_name)?;
+    }
+
+    fmt_warning_lines(
+        report
+            .warnings()
+            .map(|(entry, warning)| (entry.descriptor(), warning)),
+        f,
+        style,
+    )?;
+
+    writeln!(
+        f,
+        "{note}Note: full required environment description:{reset}"
+    )?;
+    for entry in report.invalid().chain(report.valid()) {
+        writeln!(f, "- {}", entry.descriptor())?;
+        // A single step means the variable is read as text, which isn't worth a line
+        if entry.layers().steps().len() > 1 {
+            writeln!(
+                f,
+                "  {note}layers: {}, output: {}{reset}",
+                entry.layers(),
+                entry.layers().output_type()
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn var_warnings(
+    warnings: &InitWarnings,
+) -> impl Iterator<Item = (&VarDescriptor, &ReadWarning)> + Clone {
+    warnings
+        .iter()
+        .map(|warning| (warning.descriptor(), warning.warning()))
+}
+
+/// Writes the warnings section, if there is any warning.
+fn fmt_warning_lines<'w, I>(warnings: I, f: &mut fmt::Formatter<'_>, style: &Style) -> fmt::Result
+where
+    I: Iterator<Item = (&'w VarDescriptor, &'w ReadWarning)> + Clone,
+{
+    let Style {
+        warning: yellow,
+        name,
+        reset,
+        ..
+    } = style;
+
+    let count = warnings.clone().count();
+    if count == 0 {
+        return Ok(());
+    }
+
+    writeln!(
+        f,
+        "{yellow}Got {} warning{}{reset}",
+        count,
+        if count > 1 { "s" } else { "" }
+    )?;
+    for (descriptor, warning) in warnings {
+        writeln!(f, "- {name}`{}`{reset}: {warning}", descriptor.var_name)?;
+    }
+
+    Ok(())
+}
+
+static FORMATTER: RwLock<Option<Arc<dyn ReportFormatter>>> = RwLock::new(None);
+
+/// Sets the formatter used globally for the reports.
+///
+/// It is used by the [`Display`](fmt::Display) implementation of the initialization errors, and
+/// by the panic message of [`Layer::get`](crate::Layer::get).
+///
+/// ## Example
+///
+/// ```
+/// use mkenv::report::{self, Compact};
+///
+/// report::set_formatter(Compact);
+/// ```
+pub fn set_formatter<F: ReportFormatter + 'static>(formatter: F) {
+    *FORMATTER.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(formatter));
+}
+
+/// Resets the global formatter to the default one, [`Plain`].
+pub fn reset_formatter() {
+    *FORMATTER.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Returns the formatter used globally for the reports.
+///
+/// See [`set_formatter`] for more information.
+pub fn formatter() -> Arc<dyn ReportFormatter> {
+    FORMATTER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| Arc::new(Plain))
+}
+
+/// A displayable version of a report, using a specific formatter.
+///
+/// It is returned by [`InitReport::display_with`].
+pub struct DisplayReport<'r, 'a> {
+    pub(crate) report: &'r InitReport<'a>,
+    pub(crate) formatter: &'r dyn ReportFormatter,
+    pub(crate) as_error: bool,
+}
+
+impl fmt::Display for DisplayReport<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.as_error {
+            self.formatter.fmt_init_error(self.report, f)
+        } else {
+            self.formatter.fmt_report(self.report, f)
+        }
+    }
+}
+
+/// A displayable version of the warnings of a read, using a specific formatter.
+///
+/// It is returned by [`InitWarnings::display_with`].
+pub struct DisplayWarnings<'r> {
+    pub(crate) warnings: &'r InitWarnings,
+    pub(crate) formatter: &'r dyn ReportFormatter,
+}
+
+impl fmt::Display for DisplayWarnings<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.formatter.fmt_warnings(self.warnings, f)
+    }
+}
+
+/// Displays the error of the read of a single configuration value with the global formatter.
+pub(crate) struct DisplayVarError<'a, E> {
+    pub(crate) descriptor: &'a VarDescriptor,
+    pub(crate) type_name: &'static str,
+    pub(crate) error: &'a E,
+}
+
+impl<E: fmt::Display> fmt::Display for DisplayVarError<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter().fmt_var_error(self.descriptor, self.type_name, self.error, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt, panic};
+
+    use crate::{
+        VarDescriptor,
+        exec::fmt_exec_results,
+        prelude::*,
+        report::{Ansi, Compact, InitReport, Plain, ReportFormatter, Tree},
+        tests::{with_env, with_formatter},
+    };
+
+    crate::make_config! {
+        struct TestConfig {
+            user: {
+                var_name: "__TEST_FORMAT_USER",
+                description: "The user",
+            },
+            port: {
+                var_name: "__TEST_FORMAT_PORT",
+                layers: [parsed_from_str<u16>()],
+            },
+        }
+    }
+
+    fn report_with<F>(f: F) -> String
+    where
+        F: FnOnce(&crate::report::InitReport<'_>) -> String,
+    {
+        let config = TestConfig::define();
+        let report = with_env([("__TEST_FORMAT_PORT", "80a")], || config.init_report());
+        f(&report)
+    }
+
+    #[test]
+    fn assert_plain_format() {
+        let out = report_with(|report| report.display_with(&Plain).to_string());
+        assert_eq!(
+            out,
+            "Got 2 incorrect variables\n\
+             - `__TEST_FORMAT_USER`: environment variable not found\n\
+             - `__TEST_FORMAT_PORT`: parse error: invalid digit found in string\n\
+             Got 0 valid variable\n\
+             Note: full required environment description:\n\
+             - `__TEST_FORMAT_USER`: The user\n\
+             - `__TEST_FORMAT_PORT`\n\
+             \x20 layers: text → parse u16, output: u16\n"
+        );
+    }
+
+    #[test]
+    fn assert_ansi_format() {
+        let out = report_with(|report| report.display_with(&Ansi).to_string());
+        assert!(out.starts_with("\x1b[1;31mGot 2 incorrect variables\x1b[0m\n"));
+        assert!(out.contains("- \x1b[1m`__TEST_FORMAT_PORT`\x1b[0m: parse error"));
+    }
+
+    #[test]
+    fn assert_compact_format() {
+        let out = report_with(|report| report.display_with(&Compact).to_string());
+        assert_eq!(
+            out,
+            "level=error var=__TEST_FORMAT_USER kind=not_present \
+             error=\"environment variable not found\"\n\
+             level=error var=__TEST_FORMAT_PORT kind=parse \
+             error=\"parse error: invalid digit found in string\"\n"
+        );
+    }
+
+    #[test]
+    fn assert_similar_vars() {
+        let config = TestConfig::define();
+        let report = with_env(
+            [
+                ("__TEST_FORMAT_PORT", "8080"),
+                ("__TEST_FORMAT_USERNAME", "hunter2"),
+                ("__test_format_user", "hunter2"),
+            ],
+            || config.init_report(),
+        );
+
+        let plain = report.display_with(&Plain).to_string();
+        assert!(plain.starts_with(
+            "Got 1 incorrect variable\n\
+             - `__TEST_FORMAT_USER`: environment variable not found \
+             (did you mean `__TEST_FORMAT_USERNAME` or `__test_format_user`?)\n"
+        ));
+        let compact = report.display_with(&Compact).to_string();
+        assert_eq!(
+            compact,
+            "level=error var=__TEST_FORMAT_USER kind=not_present \
+             error=\"environment variable not found\" \
+             similar=\"__TEST_FORMAT_USERNAME,__test_format_user\"\n"
+        );
+        assert!(!plain.contains("hunter2") && !compact.contains("hunter2"));
+    }
+
+    #[test]
+    fn assert_tree_format() {
+        crate::make_config! {
+            struct DbConfig {
+                url: {
+                    var_name: "__TEST_TREE_DB_URL",
+                },
+                pool: {
+                    var_name: "__TEST_TREE_DB_POOL",
+                    layers: [parsed_from_str<u8>(), or_default_val(|| 4)],
+                },
+            }
+        }
+
+        crate::make_config! {
+            struct RedisConfig {
+                url: {
+                    var_name: "__TEST_TREE_REDIS_URL",
+                },
+                pool: {
+                    var_name: "__TEST_TREE_REDIS_POOL",
+                    layers: [parsed_from_str<u8>(), or_default_val(|| 4)],
+                },
+            }
+        }
+
+        crate::make_config! {
+            struct CacheConfig {
+                redis: { RedisConfig },
+            }
+        }
+
+        crate::make_config! {
+            struct AppConfig {
+                db: { DbConfig },
+                user: {
+                    var_name: "__TEST_TREE_USER",
+                },
+                cache: { CacheConfig },
+            }
+        }
+
+        let config = AppConfig::define();
+        let report = with_env(
+            [
+                ("__TEST_TREE_DB_URL", "postgres://"),
+                ("__TEST_TREE_REDIS_URL", "redis://"),
+            ],
+            || config.init_report(),
+        );
+
+        assert_eq!(
+            report.display_with(&Tree).to_string(),
+            "Got 1 incorrect variable, 4 valid variables\n\
+             ├── db\n\
+             │   ├── url `__TEST_TREE_DB_URL`: ok\n\
+             │   └── pool `__TEST_TREE_DB_POOL`: ok (default used)\n\
+             ├── user `__TEST_TREE_USER`: environment variable not found\n\
+             └── cache\n\
+             \x20   └── redis\n\
+             \x20       ├── url `__TEST_TREE_REDIS_URL`: ok\n\
+             \x20       └── pool `__TEST_TREE_REDIS_POOL`: ok (default used)\n"
+        );
+    }
+
+    #[test]
+    fn assert_secret_default_hidden() {
+        crate::make_config! {
+            struct SecretConfig {
+                user: {
+                    var_name: "__TEST_FORMAT_SECRET_USER",
+                },
+                token: {
+                    var_name: "__TEST_FORMAT_SECRET_TOKEN",
+                    secret: true,
+                    layers: [or_default_val(|| "hunter2".to_owned())],
+                },
+                password: {
+                    var_name: "__TEST_FORMAT_SECRET_PASSWORD",
+                    secret: true,
+                    default_val_fmt: "hunter2",
+                    layers: [or_default_val(|| "hunter2".to_owned())],
+                },
+            }
+        }
+
+        let config = SecretConfig::define();
+        let (init_msg, json) = with_env([], || {
+            let init_msg = config.try_init().unwrap_err().to_string();
+            let json = fmt_exec_results(config.init_raw()).json().to_string();
+            (init_msg, json)
+        });
+        assert!(init_msg.contains("- `__TEST_FORMAT_SECRET_TOKEN`\n"));
+        assert!(init_msg.contains("- `__TEST_FORMAT_SECRET_PASSWORD` (default: <secret>)\n"));
+        assert!(json.contains(concat!(
+            r#""name":"__TEST_FORMAT_SECRET_PASSWORD","path":"password","#,
+            r#""description":null,"default":null,"#,
+        )));
+        assert!(!init_msg.contains("hunter2") && !json.contains("hunter2"));
+    }
+
+    #[test]
+    fn assert_global_formatter() {
+        struct Custom;
+
+        impl ReportFormatter for Custom {
+            fn fmt_report(
+                &self,
+                report: &InitReport<'_>,
+                f: &mut fmt::Formatter<'_>,
+            ) -> fmt::Result {
+                write!(f, "{} invalid", report.invalid_count())
+            }
+
+            fn fmt_var_error(
+                &self,
+                descriptor: &VarDescriptor,
+                _type_name: &str,
+                error: &dyn fmt::Display,
+                f: &mut fmt::Formatter<'_>,
+            ) -> fmt::Result {
+                write!(f, "{}: {error}", descriptor.var_name)
+            }
+        }
+
+        let config = TestConfig::define();
+        let (init_msg, panic_msg) = with_formatter(Custom, [("__TEST_FORMAT_PORT", "80a")], || {
+            let init_msg = config.try_init().unwrap_err().to_string();
+            let panic_msg = panic::catch_unwind(|| config.port.get()).unwrap_err();
+            (init_msg, panic_msg.downcast::<String>().unwrap())
+        });
+        assert_eq!(
+            init_msg,
+            "Error during configuration initialization:\n2 invalid"
+        );
+        assert_eq!(
+            *panic_msg,
+            "__TEST_FORMAT_PORT: parse error: invalid digit found in string"
+        );
+
+        // The formatter was reset
+        let init_msg = with_env([("__TEST_FORMAT_PORT", "80a")], || {
+            config.try_init().unwrap_err().to_string()
+        });
+        assert!(
+            init_msg.starts_with("Error during configuration initialization:\nGot 2 incorrect")
+        );
+    }
+}
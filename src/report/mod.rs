@@ -0,0 +1,792 @@
+//! Contains the model of the report of a whole configuration read.
+
+pub mod explain;
+pub mod format;
+
+use std::{env, error::Error, fmt, slice};
+
+use crate::{
+    descriptor::{LayerDescription, VarDescriptor},
+    distance,
+    error::{DuplicateVarError, ErrorKind},
+    exec::{ExecResult, InitMode, LazyLayers},
+    layer::{ReadStep, ReadTrace, ReadWarning},
+};
+
+pub use explain::Explanation;
+pub use format::{
+    Ansi, Auto, Compact, DisplayReport, DisplayWarnings, Plain, ReportFormatter, Tree, formatter,
+    reset_formatter, set_formatter,
+};
+
+/// The report of a whole configuration read.
+///
+/// It contains an entry for each variable of the configuration, in their declaration order. It is
+/// meant to be used to write custom reports, e.g. to log them in a specific format.
+///
+/// To get it, see [`ConfigInitializer::init_report`][1].
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::{prelude::*, error::ErrorKind};
+/// mkenv::make_config! {
+///   struct AppConfig {
+///     port: {
+///       var_name: "REPORT_PORT",
+///       layers: [parsed_from_str<u16>(), or_default_val(|| 8080)],
+///     },
+///     db_url: { var_name: "REPORT_DB_URL" },
+///   }
+/// }
+///
+/// let config = AppConfig::define();
+/// let report = config.init_report();
+///
+/// assert_eq!(report.valid_count(), 1);
+/// assert_eq!(report.defaulted_count(), 1);
+/// for entry in report.invalid() {
+///   assert_eq!(entry.descriptor().var_name, "REPORT_DB_URL");
+///   assert_eq!(entry.error_kind(), Some(ErrorKind::NotPresent));
+/// }
+/// ```
+///
+/// [1]: crate::exec::ConfigInitializer::init_report
+#[derive(Debug)]
+pub struct InitReport<'a> {
+    pub(crate) entries: Vec<ReportEntry<'a>>,
+    pub(crate) unchecked: usize,
+}
+
+impl<'a> InitReport<'a> {
+    /// Builds the report from the results of a configuration read.
+    ///
+    /// Every result is evaluated. This is the same as calling [`InitReport::with_mode`] with
+    /// [`InitMode::CollectAll`].
+    #[inline]
+    pub fn new<I>(results: I) -> Self
+    where
+        I: IntoIterator<Item = ExecResult<'a>>,
+    {
+        Self::with_mode(results, InitMode::CollectAll)
+    }
+
+    /// Builds the report from the results of a configuration read, using the provided mode.
+    ///
+    /// With [`InitMode::FailFast`], the results are evaluated until the first error. The number
+    /// of remaining results is taken from the lower bound of the [size hint][1] of the
+    /// iterator, which is exact for the configurations generated by [`make_config!`][2].
+    ///
+    /// [1]: Iterator::size_hint
+    /// [2]: crate::make_config
+    pub fn with_mode<I>(results: I, mode: InitMode) -> Self
+    where
+        I: IntoIterator<Item = ExecResult<'a>>,
+    {
+        let mut results = results.into_iter();
+        let mut entries = Vec::with_capacity(results.size_hint().0);
+
+        for result in results.by_ref() {
+            // The macro rejects the duplicates at compile time, but not the custom configurations
+            // The deprecated aliases are read too, so they can't be shared either
+            let duplicate = entries
+                .iter()
+                .find_map(|entry: &ReportEntry<'_>| {
+                    let var_name = entry
+                        .descriptor
+                        .var_names()
+                        .find(|name| result.config.var_names().any(|other| other == *name))?;
+                    Some((entry, var_name))
+                })
+                .map(|(first, var_name)| EntryError {
+                    error: Box::new(DuplicateVarError {
+                        var_name,
+                        first_path: first.path.clone(),
+                        path: result.path.clone(),
+                    }),
+                    kind: ErrorKind::Duplicate,
+                });
+
+            let entry = ReportEntry {
+                descriptor: result.config.clone(),
+                error: duplicate.or_else(|| {
+                    result.error.map(|error| EntryError {
+                        error,
+                        kind: result.kind.unwrap_or(ErrorKind::Other),
+                    })
+                }),
+                trace: result.trace,
+                path: result.path,
+                layers: result.layers,
+                similar_vars: Vec::new(),
+            };
+            let failed = !entry.is_valid();
+            entries.push(entry);
+
+            if failed && mode == InitMode::FailFast {
+                break;
+            }
+        }
+
+        add_similar_vars(&mut entries);
+
+        Self {
+            entries,
+            unchecked: results.size_hint().0,
+        }
+    }
+
+    /// Returns an iterator over all the entries, in their declaration order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, ReportEntry<'a>> {
+        self.entries.iter()
+    }
+
+    /// Returns an iterator over the entries of the variables that were correctly read.
+    pub fn valid(&self) -> impl Iterator<Item = &ReportEntry<'a>> {
+        self.iter().filter(|entry| entry.is_valid())
+    }
+
+    /// Returns an iterator over the entries of the variables that failed to read.
+    pub fn invalid(&self) -> impl Iterator<Item = &ReportEntry<'a>> {
+        self.iter().filter(|entry| !entry.is_valid())
+    }
+
+    /// Returns an iterator over the entries of the variables that were resolved from a default
+    /// value.
+    pub fn defaulted(&self) -> impl Iterator<Item = &ReportEntry<'a>> {
+        self.iter().filter(|entry| entry.used_default())
+    }
+
+    /// Returns the total number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the report has no entry.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of variables that were correctly read.
+    #[inline]
+    pub fn valid_count(&self) -> usize {
+        self.valid().count()
+    }
+
+    /// Returns the number of variables that failed to read.
+    #[inline]
+    pub fn invalid_count(&self) -> usize {
+        self.invalid().count()
+    }
+
+    /// Returns the number of variables that were resolved from a default value.
+    #[inline]
+    pub fn defaulted_count(&self) -> usize {
+        self.defaulted().count()
+    }
+
+    /// Returns the number of variables that were left unchecked.
+    ///
+    /// This is always `0`, unless the read was made with [`InitMode::FailFast`] and stopped
+    /// before the last variable.
+    #[inline]
+    pub fn unchecked_count(&self) -> usize {
+        self.unchecked
+    }
+
+    /// Returns whether every variable was correctly read.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.invalid().next().is_none()
+    }
+
+    /// Returns an iterator over the warnings of the read, with the entry they relate to.
+    pub fn warnings(&self) -> impl Iterator<Item = (&ReportEntry<'a>, &ReadWarning)> + Clone {
+        self.iter()
+            .flat_map(|entry| entry.warnings().iter().map(move |warning| (entry, warning)))
+    }
+
+    /// Returns the total number of warnings.
+    #[inline]
+    pub fn warning_count(&self) -> usize {
+        self.warnings().count()
+    }
+
+    /// Extracts the warnings of the read.
+    pub fn to_warnings(&self) -> InitWarnings {
+        InitWarnings {
+            warnings: self
+                .warnings()
+                .map(|(entry, warning)| VarWarning {
+                    descriptor: entry.descriptor.clone(),
+                    warning: warning.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the warnings about the environment variables starting with one of the provided
+    /// prefixes, but not read by the configuration, sorted by name.
+    ///
+    /// Each warning relates to the closest declared variable, ignoring the prefix. The unknown
+    /// variables that aren't similar to any declared one are left out.
+    pub(crate) fn unknown_var_warnings(&self, prefixes: &[&str]) -> Vec<VarWarning> {
+        if prefixes.is_empty() {
+            return Vec::new();
+        }
+
+        // Includes the other names read by the entries, e.g. the deprecated aliases
+        let read_names = self
+            .iter()
+            .flat_map(|entry| entry.trace.steps())
+            .filter_map(|step| match step {
+                ReadStep::Var { var_name, .. } => Some(*var_name),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut unknown_names = env::vars_os()
+            .filter_map(|(name, _)| name.into_string().ok())
+            .filter_map(|name| {
+                let prefix = *prefixes.iter().find(|prefix| name.starts_with(*prefix))?;
+                Some((name, prefix))
+            })
+            .filter(|(name, _)| {
+                !self.iter().any(|entry| entry.descriptor.var_name == name)
+                    && !read_names.contains(&name.as_str())
+            })
+            .collect::<Vec<_>>();
+        unknown_names.sort_unstable();
+
+        unknown_names
+            .into_iter()
+            .filter_map(|(var_name, prefix)| {
+                // The shared prefix would make any name look similar
+                let unprefixed = |name: &'a str| name.strip_prefix(prefix).unwrap_or(name);
+                let name = &var_name[prefix.len()..];
+                let entry = self
+                    .iter()
+                    .map(|entry| (entry, unprefixed(entry.descriptor.var_name)))
+                    .filter(|(_, declared)| distance::is_similar(name, declared))
+                    .min_by_key(|(_, declared)| distance::edit_distance(name, declared))
+                    .map(|(entry, _)| entry)?;
+                Some(VarWarning {
+                    descriptor: entry.descriptor.clone(),
+                    warning: ReadWarning::UnknownVar { var_name },
+                })
+            })
+            .collect()
+    }
+
+    /// Returns a displayable version of the report, using the provided formatter.
+    ///
+    /// The [`Display`](std::fmt::Display) implementation of the initialization errors uses the
+    /// [global formatter](set_formatter) instead.
+    #[inline]
+    pub fn display_with<'r>(&'r self, formatter: &'r dyn ReportFormatter) -> DisplayReport<'r, 'a> {
+        DisplayReport {
+            report: self,
+            formatter,
+            as_error: false,
+        }
+    }
+
+    /// Converts the report into a report that doesn't borrow anything.
+    ///
+    /// The errors are converted into their message.
+    pub(crate) fn into_owned(self) -> InitReport<'static> {
+        InitReport {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|entry| ReportEntry {
+                    descriptor: entry.descriptor,
+                    error: entry.error.map(|err| EntryError {
+                        error: err.error.to_string().into(),
+                        kind: err.kind,
+                    }),
+                    trace: entry.trace,
+                    path: entry.path,
+                    layers: entry.layers.into_owned(),
+                    similar_vars: entry.similar_vars,
+                })
+                .collect(),
+            unchecked: self.unchecked,
+        }
+    }
+}
+
+/// Fills the names of the environment variables similar to the missing ones.
+///
+/// Only the names are read from the environment, so no value can leak in the report.
+fn add_similar_vars(entries: &mut [ReportEntry<'_>]) {
+    let is_missing = |entry: &ReportEntry<'_>| entry.error_kind() == Some(ErrorKind::NotPresent);
+    if !entries.iter().any(is_missing) {
+        return;
+    }
+
+    // The declared variables are already reported on their own
+    let mut set_names = env::vars_os()
+        .filter_map(|(name, _)| name.into_string().ok())
+        .filter(|name| {
+            !entries
+                .iter()
+                .any(|entry| entry.descriptor.var_name == name)
+        })
+        .collect::<Vec<_>>();
+    set_names.sort_unstable();
+
+    for entry in entries.iter_mut().filter(|entry| is_missing(entry)) {
+        entry.similar_vars = set_names
+            .iter()
+            .filter(|name| distance::is_similar(name, entry.descriptor.var_name))
+            .cloned()
+            .collect();
+    }
+}
+
+impl<'r, 'a> IntoIterator for &'r InitReport<'a> {
+    type Item = &'r ReportEntry<'a>;
+    type IntoIter = slice::Iter<'r, ReportEntry<'a>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The entry of a single variable in an [`InitReport`].
+#[derive(Debug)]
+pub struct ReportEntry<'a> {
+    pub(crate) descriptor: VarDescriptor,
+    pub(crate) error: Option<EntryError<'a>>,
+    pub(crate) trace: ReadTrace,
+    pub(crate) path: Vec<&'static str>,
+    pub(crate) layers: LazyLayers<'a>,
+    pub(crate) similar_vars: Vec<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct EntryError<'a> {
+    pub(crate) error: Box<dyn Error + Send + Sync + 'a>,
+    pub(crate) kind: ErrorKind,
+}
+
+impl<'a> ReportEntry<'a> {
+    /// Returns the descriptor of the variable.
+    #[inline]
+    pub fn descriptor(&self) -> &VarDescriptor {
+        &self.descriptor
+    }
+
+    /// Returns the path of the variable in the configuration.
+    ///
+    /// See [`ExecResult::path`] for more information.
+    #[inline]
+    pub fn path(&self) -> &[&'static str] {
+        &self.path
+    }
+
+    /// Returns whether the variable was correctly read.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the error of the read, if it failed.
+    #[inline]
+    pub fn error(&self) -> Option<&(dyn Error + Send + Sync + 'a)> {
+        self.error.as_ref().map(|err| &*err.error)
+    }
+
+    /// Returns the kind of the error of the read, if it failed.
+    #[inline]
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        self.error.as_ref().map(|err| err.kind)
+    }
+
+    /// Returns whether the variable was resolved from a default value.
+    #[inline]
+    pub fn used_default(&self) -> bool {
+        self.trace.used_default()
+    }
+
+    /// Returns the warnings of the read.
+    #[inline]
+    pub fn warnings(&self) -> &[ReadWarning] {
+        self.trace.warnings()
+    }
+
+    /// Returns the trace of the read.
+    #[inline]
+    pub fn trace(&self) -> &ReadTrace {
+        &self.trace
+    }
+
+    /// Returns the description of the chain of layers of the variable.
+    #[inline]
+    pub fn layers(&self) -> &LayerDescription {
+        self.layers.get()
+    }
+
+    /// Returns the names of the environment variables similar to this one, sorted by name, if it
+    /// isn't set.
+    ///
+    /// They are set, but not declared by the configuration, e.g. `DATABASE_URL` or `db_url`
+    /// for `DB_URL`, so they may be the intended ones. Their values are never read.
+    #[inline]
+    pub fn similar_vars(&self) -> &[String] {
+        &self.similar_vars
+    }
+}
+
+/// The warnings of a successful configuration read.
+///
+/// It is returned by [`ConfigInitializer::try_init`][1]. Its [`Display`](fmt::Display)
+/// implementation uses the [global formatter](set_formatter).
+///
+/// ## Example
+///
+/// ```
+/// # use mkenv::{prelude::*, ReadWarning};
+/// # unsafe { std::env::set_var("WARNINGS_PORT", "80a"); }
+/// mkenv::make_config! {
+///   struct AppConfig {
+///     port: {
+///       var_name: "WARNINGS_PORT",
+///       layers: [parsed_from_str<u16>(), or_default_val(|| 8080)],
+///     },
+///   }
+/// }
+///
+/// let config = AppConfig::define();
+/// let warnings = config.try_init().unwrap();
+/// # unsafe { std::env::remove_var("WARNINGS_PORT"); }
+/// let warning = warnings.iter().next().unwrap();
+/// assert_eq!(warning.descriptor().var_name, "WARNINGS_PORT");
+/// assert_eq!(*warning.warning(), ReadWarning::DefaultOnInvalid);
+/// ```
+///
+/// [1]: crate::exec::ConfigInitializer::try_init
+#[derive(Debug, Clone, Default)]
+pub struct InitWarnings {
+    pub(crate) warnings: Vec<VarWarning>,
+}
+
+impl InitWarnings {
+    /// Returns an iterator over the warnings, in the declaration order of their variable, then
+    /// the [unknown variables](crate::ReadWarning::UnknownVar) sorted by name.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, VarWarning> {
+        self.warnings.iter()
+    }
+
+    /// Returns the number of warnings.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.warnings.len()
+    }
+
+    /// Returns whether there is no warning.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    /// Returns a displayable version of the warnings, using the provided formatter.
+    #[inline]
+    pub fn display_with<'r>(&'r self, formatter: &'r dyn ReportFormatter) -> DisplayWarnings<'r> {
+        DisplayWarnings {
+            warnings: self,
+            formatter,
+        }
+    }
+}
+
+impl<'r> IntoIterator for &'r InitWarnings {
+    type Item = &'r VarWarning;
+    type IntoIter = slice::Iter<'r, VarWarning>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Display for InitWarnings {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter().fmt_warnings(self, f)
+    }
+}
+
+/// A warning about the read of a variable, in [`InitWarnings`].
+#[derive(Debug, Clone)]
+pub struct VarWarning {
+    pub(crate) descriptor: VarDescriptor,
+    pub(crate) warning: ReadWarning,
+}
+
+impl VarWarning {
+    /// Returns the descriptor of the variable.
+    #[inline]
+    pub fn descriptor(&self) -> &VarDescriptor {
+        &self.descriptor
+    }
+
+    /// Returns the warning.
+    #[inline]
+    pub fn warning(&self) -> &ReadWarning {
+        &self.warning
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use crate::{
+        ReadWarning,
+        error::ErrorKind,
+        exec::{ConfigInitializer, ExecResult, InitOptions, Prefixed},
+        prelude::*,
+        report::Plain,
+        tests::with_env,
+    };
+
+    crate::make_config! {
+        struct TestConfig {
+            user: {
+                var_name: "__TEST_REPORT_USER",
+            },
+            port: {
+                var_name: "__TEST_REPORT_PORT",
+                layers: [parsed_from_str<u16>(), or_default_val(|| 8080)],
+            },
+            timeout: {
+                var_name: "__TEST_REPORT_TIMEOUT",
+                layers: [parsed_from_str<u64>(), or_default_val(|| 30), cached()],
+            },
+        }
+    }
+
+    #[test]
+    fn assert_report_entries() {
+        let config = TestConfig::define();
+        let report = with_env(
+            [
+                ("__TEST_REPORT_PORT", "80a"),
+                ("__TEST_REPORT_TIMEOUT", "5"),
+            ],
+            || config.init_report(),
+        );
+
+        assert_eq!(report.len(), 3);
+        assert_eq!(report.valid_count(), 2);
+        assert_eq!(report.invalid_count(), 1);
+        assert_eq!(report.defaulted_count(), 1);
+        assert!(!report.is_ok());
+
+        itertools::assert_equal(
+            report.iter().map(|entry| entry.descriptor().var_name),
+            [
+                "__TEST_REPORT_USER",
+                "__TEST_REPORT_PORT",
+                "__TEST_REPORT_TIMEOUT",
+            ],
+        );
+        itertools::assert_equal(
+            report.defaulted().map(|entry| entry.descriptor().var_name),
+            ["__TEST_REPORT_PORT"],
+        );
+        itertools::assert_equal(
+            report.invalid().map(|entry| entry.error_kind()),
+            [Some(ErrorKind::NotPresent)],
+        );
+    }
+
+    #[test]
+    fn assert_cached_trace() {
+        let config = TestConfig::define();
+
+        for _ in 0..2 {
+            let report = with_env([("__TEST_REPORT_USER", "foo")], || config.init_report());
+            assert!(report.is_ok());
+            itertools::assert_equal(
+                report.defaulted().map(|entry| entry.descriptor().var_name),
+                ["__TEST_REPORT_PORT", "__TEST_REPORT_TIMEOUT"],
+            );
+        }
+    }
+
+    #[test]
+    fn assert_init_warnings() {
+        let config = TestConfig::define();
+
+        let warnings = with_env(
+            [("__TEST_REPORT_USER", ""), ("__TEST_REPORT_PORT", "80a")],
+            || config.try_init().unwrap(),
+        );
+
+        itertools::assert_equal(
+            warnings
+                .iter()
+                .map(|warning| (warning.descriptor().var_name, warning.warning().clone())),
+            [
+                ("__TEST_REPORT_USER", ReadWarning::EmptyValue),
+                ("__TEST_REPORT_PORT", ReadWarning::DefaultOnInvalid),
+            ],
+        );
+        assert_eq!(
+            warnings.display_with(&Plain).to_string(),
+            "Got 2 warnings\n\
+             - `__TEST_REPORT_USER`: the variable is set, but empty\n\
+             - `__TEST_REPORT_PORT`: invalid value, the default value was used\n"
+        );
+    }
+
+    #[test]
+    fn assert_duplicate_var_name() {
+        // Not generated by the macro, so not checked at compile time
+        struct CustomConfig {
+            http_port: TextVar,
+            metrics_port: TextVar,
+        }
+
+        impl ConfigInitializer for CustomConfig {
+            type Iter<'a> = Vec<ExecResult<'a>>;
+
+            fn init_raw(&self) -> Self::Iter<'_> {
+                let http = iter::once(ExecResult::from_config(&self.http_port));
+                let metrics = iter::once(ExecResult::from_config(&self.metrics_port));
+                Prefixed::new("http", http)
+                    .chain(Prefixed::new("metrics", metrics))
+                    .collect()
+            }
+        }
+
+        let config = CustomConfig {
+            http_port: TextVar::from_var_name("__TEST_REPORT_DUP_PORT"),
+            metrics_port: TextVar::from_var_name("__TEST_REPORT_DUP_PORT"),
+        };
+        let err = with_env([("__TEST_REPORT_DUP_PORT", "8080")], || {
+            config.try_init().unwrap_err()
+        });
+
+        itertools::assert_equal(
+            err.report().iter().map(|entry| entry.error_kind()),
+            [None, Some(ErrorKind::Duplicate)],
+        );
+        assert_eq!(
+            err.display_with(&Plain).to_string(),
+            "Error during configuration initialization:\n\
+             Got 1 incorrect variable\n\
+             - `__TEST_REPORT_DUP_PORT`: the environment variable `__TEST_REPORT_DUP_PORT` \
+             of `metrics` is already declared by `http`\n\
+             Got 1 valid variable\n\
+             - `__TEST_REPORT_DUP_PORT`\n\
+             Note: full required environment description:\n\
+             - `__TEST_REPORT_DUP_PORT`\n\
+             - `__TEST_REPORT_DUP_PORT`\n"
+        );
+    }
+
+    #[test]
+    fn assert_duplicate_alias() {
+        struct CustomConfig {
+            port: TextVar,
+            http_port: TextVar,
+        }
+
+        impl ConfigInitializer for CustomConfig {
+            type Iter<'a> = Vec<ExecResult<'a>>;
+
+            fn init_raw(&self) -> Self::Iter<'_> {
+                vec![
+                    ExecResult::from_config(&self.port),
+                    ExecResult::from_config(&self.http_port),
+                ]
+            }
+        }
+
+        let config = CustomConfig {
+            port: TextVar::from_var_name("__TEST_REPORT_ALIAS_PORT"),
+            http_port: TextVar::from_var_name("__TEST_REPORT_ALIAS_HTTP_PORT")
+                .deprecated_alias("__TEST_REPORT_ALIAS_PORT"),
+        };
+        let err = with_env([("__TEST_REPORT_ALIAS_PORT", "8080")], || {
+            config.try_init().unwrap_err()
+        });
+
+        itertools::assert_equal(
+            err.report().iter().map(|entry| entry.error_kind()),
+            [None, Some(ErrorKind::Duplicate)],
+        );
+        let entry = err.report().iter().nth(1).unwrap();
+        assert!(
+            entry
+                .error()
+                .unwrap()
+                .to_string()
+                .starts_with("the environment variable `__TEST_REPORT_ALIAS_PORT`")
+        );
+    }
+
+    #[test]
+    fn assert_unknown_vars() {
+        crate::make_config! {
+            struct UnknownConfig {
+                db_url: {
+                    var_name: "__TEST_UNKNOWN_DB_URL",
+                },
+                db_pool: {
+                    var_name: "__TEST_UNKNOWN_DB_POOL",
+                    layers: [optional()],
+                },
+                host: {
+                    var_name: "__TEST_UNKNOWN_HOST",
+                    deprecated_alias: "__TEST_UNKNOWN_OLD_HOST",
+                },
+            }
+        }
+
+        let config = UnknownConfig::define();
+        let warnings = with_env(
+            [
+                ("__TEST_UNKNOWN_DB_URL", "postgres://"),
+                ("__TEST_UNKNOWN_DB_ULR", "postgres://"),
+                ("__TEST_UNKNOWN_DB_POLO", "4"),
+                ("__TEST_UNKNOWN_OLD_HOST", "localhost"),
+                ("__TEST_UNKNOWN_LOG_LEVEL", "debug"),
+                ("__TEST_UNRELATED_DB_ULR", "postgres://"),
+            ],
+            || {
+                let options = InitOptions::new().unknown_var_prefixes(&["__TEST_UNKNOWN_"]);
+                config.try_init_with_options(options).unwrap()
+            },
+        );
+
+        itertools::assert_equal(
+            warnings
+                .iter()
+                .map(|warning| (warning.descriptor().var_name, warning.warning().as_str())),
+            [
+                ("__TEST_UNKNOWN_HOST", "deprecated_alias"),
+                ("__TEST_UNKNOWN_DB_POOL", "unknown_var"),
+                ("__TEST_UNKNOWN_DB_URL", "unknown_var"),
+            ],
+        );
+        assert_eq!(
+            warnings.display_with(&Plain).to_string(),
+            "Got 3 warnings\n\
+             - `__TEST_UNKNOWN_HOST`: read from the deprecated variable `__TEST_UNKNOWN_OLD_HOST`\n\
+             - `__TEST_UNKNOWN_DB_POOL`: the unknown variable `__TEST_UNKNOWN_DB_POLO` is set, \
+             did you mean this one?\n\
+             - `__TEST_UNKNOWN_DB_URL`: the unknown variable `__TEST_UNKNOWN_DB_ULR` is set, \
+             did you mean this one?\n"
+        );
+    }
+}
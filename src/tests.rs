@@ -1,4 +1,9 @@
-use std::{env, sync::Mutex};
+use std::{
+    env,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::report::{self, ReportFormatter};
 
 macro_rules! assert_matches {
     ($left:expr, $(|)? $( $pattern:pat_param )|+ $( if $guard: expr )? $(,)?) => {
@@ -17,6 +22,11 @@ macro_rules! assert_matches {
 
 pub(crate) use assert_matches;
 
+fn lock() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap()
+}
+
 /// Locks the environment to execute the provided function.
 ///
 /// This is to avoid having the environment being accessed by two tests at the same time.
@@ -25,13 +35,42 @@ where
     F: FnOnce() -> R,
     I: IntoIterator<Item = (&'a str, &'a str)>,
 {
-    static LOCK: Mutex<()> = Mutex::new(());
+    let _guard = lock();
+    set_env(defs, f)
+}
+
+/// Same as [`with_env`], but also sets the global formatter during the function.
+///
+/// It is reset afterwards, even if the function panics. The tests relying on the default
+/// formatter must then format the reports while holding the lock, i.e. in [`with_env`].
+pub(crate) fn with_formatter<'a, T, I, F, R>(formatter: T, defs: I, f: F) -> R
+where
+    T: ReportFormatter + 'static,
+    F: FnOnce() -> R,
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            report::reset_formatter();
+        }
+    }
+
+    let _guard = lock();
+    report::set_formatter(formatter);
+    let _reset = Reset;
+    set_env(defs, f)
+}
 
+fn set_env<'a, I, F, R>(defs: I, f: F) -> R
+where
+    F: FnOnce() -> R,
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
     let iter = defs.into_iter();
     let mut vars_to_unset = Vec::with_capacity(iter.size_hint().0);
 
-    let _guard = LOCK.lock().unwrap();
-
     for (key, value) in iter {
         unsafe {
             env::set_var(key, value);